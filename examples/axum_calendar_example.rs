@@ -99,25 +99,19 @@ pub async fn handle_google_oauth_redirect(
         google_redirect_uri: "http://localhost:8080/api/v1/google/oauth2/redirect",
     };
 
-    let access_token = google_workspace_apis::auth::get_acces_token(
-        &code,
-        google_cfg.google_client_secret,
-        google_cfg.google_client_id,
-        google_cfg.google_redirect_uri,
-    )
-    .await
-    .unwrap();
-
     let client_credentials = ClientCredentials {
         redirect_uri: google_cfg.google_redirect_uri.to_string(),
         client_id: google_cfg.google_client_id.to_string(),
         client_secret: google_cfg.google_client_secret.to_string(),
-        refresh_token: access_token.refresh_token.clone(),
+        refresh_token: String::new(),
     };
 
+    // Exchanges the code and copies the returned refresh token into the credentials.
     // The last parameter ditctates wether the client will refresh the token automatically using
     // the refresh token if it expires
-    let new_client = GoogleClient::new(client_credentials, access_token, true);
+    let new_client = google_workspace_apis::auth::complete_oauth(&code, &client_credentials, true)
+        .await
+        .unwrap();
     let mut guard = state.google_client.lock().await;
     *guard = Some(new_client);
     StatusCode::OK
@@ -125,8 +119,8 @@ pub async fn handle_google_oauth_redirect(
 
 async fn get_calendar_events(State(state): State<AppState>) -> Json<Vec<Event>> {
     // Create the request builder and immediately drop the lock
-    let mut google_client_guard = state.google_client.lock().await;
-    let client = google_client_guard.as_mut().unwrap();
+    let google_client_guard = state.google_client.lock().await;
+    let client = google_client_guard.as_ref().unwrap();
     let events = CalendarEventsClient::new(client)
         .get_events("primary")
         .single_events(true)
@@ -141,8 +135,8 @@ async fn get_calendar_events(State(state): State<AppState>) -> Json<Vec<Event>>
 }
 
 async fn insert_calendar_event(State(state): State<AppState>) {
-    let mut google_client_guard = state.google_client.lock().await;
-    let client = google_client_guard.as_mut().unwrap();
+    let google_client_guard = state.google_client.lock().await;
+    let client = google_client_guard.as_ref().unwrap();
     let start: EventDateTime = EventDateTime {
         date: Some("2025-07-28".to_string()),
         date_time: None,
@@ -162,8 +156,8 @@ async fn insert_calendar_event(State(state): State<AppState>) {
 }
 
 async fn update_calendar_event(State(state): State<AppState>) {
-    let mut google_client_guard = state.google_client.lock().await;
-    let client = google_client_guard.as_mut().unwrap();
+    let google_client_guard = state.google_client.lock().await;
+    let client = google_client_guard.as_ref().unwrap();
     CalendarEventsClient::new(client)
         .patch_event("primary", "3is75fqkn1uhcg5nu4bevdkfpe")
         .set_summary("Gaat niet door")
@@ -1,11 +1,37 @@
-use std::{str::FromStr, sync::Arc};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
-use anyhow::Error;
+use crate::error::Error;
 use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::refresh_acces_token;
+use crate::error::GoogleApiError;
+use crate::utils::request::QueryParams;
+
+use crate::utils::rate_limit::{RateLimiter, TokenBucket};
+
+use super::{refresh_acces_token, scopes::Scope, service_account::ServiceAccount};
+
+/// Lifetime Google gives access tokens, used when a token response doesn't include `expires_in`.
+pub const DEFAULT_EXPIRES_IN_SECS: i64 = 3600;
+
+/// Calendar used by the `*_default` calendar requests when no other calendar is configured.
+pub const DEFAULT_CALENDAR_ID: &str = "primary";
+
+/// Default margin before the actual expiry at which an access token is already considered invalid.
+pub const DEFAULT_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Content type of request bodies unless a request sets another one.
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// `User-Agent` of every request, see `GoogleClient::set_user_agent_suffix`.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!("google-workspace-apis-rs/", env!("CARGO_PKG_VERSION"));
+
+fn default_expires_in() -> i64 {
+    DEFAULT_EXPIRES_IN_SECS
+}
 
 #[derive(Debug, JsonSchema, Clone, Default, Serialize, Deserialize)]
 pub struct AccessToken {
@@ -22,7 +48,10 @@ pub struct AccessToken {
         deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
     )]
     pub access_token: String,
-    #[serde(default)]
+    /// Seconds until the access token expires.
+    /// Defaults to `DEFAULT_EXPIRES_IN_SECS` when the token response omits it, a missing value
+    /// would otherwise make the token expire immediately and trigger a refresh on every request.
+    #[serde(default = "default_expires_in")]
     pub expires_in: i64,
 
     #[serde(
@@ -54,6 +83,12 @@ pub struct ClientTokenData {
     #[serde(default)]
     pub expires_on: chrono::DateTime<chrono::Utc>,
 
+    /// When the refresh token itself expires, only set when Google reports it.
+    /// Apps in the "testing" publishing status get refresh tokens that expire after 7 days,
+    /// the user has to consent again before this moment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token_expires_on: Option<chrono::DateTime<chrono::Utc>>,
+
     #[serde(
         default,
         skip_serializing_if = "String::is_empty",
@@ -93,23 +128,130 @@ pub struct ClientCredentials {
     pub refresh_token: String,
 }
 
-#[derive(Clone, Default)]
+/// The `{"web": {...}}` or `{"installed": {...}}` shape of the client secret JSON that can be
+/// downloaded from the Google Cloud Console.
+#[derive(Deserialize)]
+struct ClientSecretFile {
+    #[serde(alias = "installed")]
+    web: ClientSecretEntry,
+}
+
+#[derive(Deserialize)]
+struct ClientSecretEntry {
+    client_id: String,
+    client_secret: String,
+    #[serde(default)]
+    redirect_uris: Vec<String>,
+}
+
+impl ClientCredentials {
+    /// Reads the credentials from the `GOOGLE_CLIENT_ID`, `GOOGLE_CLIENT_SECRET`,
+    /// `GOOGLE_REDIRECT_URI` and `GOOGLE_REFRESH_TOKEN` environment variables.
+    /// The client id and secret are required, the redirect uri and refresh token default to empty.
+    pub fn from_env() -> Result<Self, Error> {
+        let required = |key: &str| {
+            std::env::var(key)
+                .map_err(|_| Error::Auth(format!("Environment variable {key} is not set")))
+        };
+        Ok(Self {
+            client_id: required("GOOGLE_CLIENT_ID")?,
+            client_secret: required("GOOGLE_CLIENT_SECRET")?,
+            redirect_uri: std::env::var("GOOGLE_REDIRECT_URI").unwrap_or_default(),
+            refresh_token: std::env::var("GOOGLE_REFRESH_TOKEN").unwrap_or_default(),
+        })
+    }
+
+    /// Reads the credentials from a `client_secret_*.json` file as downloaded from the Google
+    /// Cloud Console. Both web and installed application files are supported, the first redirect
+    /// uri is used. The file doesn't contain a refresh token, set it once it's known.
+    pub fn from_client_secret_json(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        let contents = std::fs::read_to_string(path)?;
+        let file: ClientSecretFile = serde_json::from_str(&contents)?;
+        Ok(Self {
+            client_id: file.web.client_id,
+            client_secret: file.web.client_secret,
+            redirect_uri: file
+                .web
+                .redirect_uris
+                .into_iter()
+                .next()
+                .unwrap_or_default(),
+            refresh_token: String::new(),
+        })
+    }
+}
+
+/// A default client has no access token, requests made with it fail with a "client not
+/// authenticated" error until a token is set.
+///
+/// Requests only need a shared reference, the token is kept behind a lock internally.
+/// The client is `Send + Sync` and so are the request builders and the futures they return,
+/// so it can be shared as `Arc<GoogleClient>` and used concurrently from spawned tokio tasks
+/// or axum handlers without locking the whole client.
+///
+/// Clones share the token state: when one clone refreshes the token, or a token is set with
+/// `set_access_token`, the other clones use the new token from their next request on instead of
+/// refreshing on their own. The connection pool, rate limiter and ETag cache are shared as well.
+/// Settings like the refresh handlers, the request reason or auto refresh are copied on clone,
+/// changing them afterwards only affects the client they're changed on.
+#[derive(Clone)]
 pub struct GoogleClient {
     pub client_credentials: ClientCredentials,
-    pub access_token: Option<ClientTokenData>,
     pub req_client: reqwest::Client,
     pub auto_refresh_token: bool,
     refresh_handlers: Vec<Arc<dyn TokenRefreshHandler>>,
+    /// Shared between clones of the client, so setting it also limits clones made before.
+    rate_limiter: Arc<std::sync::RwLock<Option<Arc<dyn RateLimiter>>>>,
+    /// ETags of the responses to conditional requests by URL and query, shared between clones
+    /// of the client.
+    etag_cache: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    refresh_margin: chrono::Duration,
+    default_calendar: String,
+    request_reason: Option<String>,
+    user_agent_suffix: Option<String>,
+    auth_user: Option<u32>,
+    base_url: Option<String>,
+    service_account: Option<ServiceAccount>,
+    /// Shared between clones of the client.
+    tokens: Arc<TokenState>,
+}
+
+/// The token of a client and its clones.
+#[derive(Default)]
+struct TokenState {
+    token: std::sync::RwLock<Option<StoredToken>>,
+    /// Held while a refresh is in flight, so only one refresh runs at a time.
+    refresh_lock: tokio::sync::Mutex<()>,
+}
+
+struct StoredToken {
+    data: ClientTokenData,
+    /// Monotonic expiry of tokens obtained by this process, unlike `expires_on` it isn't
+    /// affected when the system clock jumps. None for tokens with an unknown lifetime.
+    expires_at: Option<std::time::Instant>,
+}
+
+impl From<AccessToken> for StoredToken {
+    fn from(token: AccessToken) -> Self {
+        let expires_at = u64::try_from(token.expires_in).ok().and_then(|secs| {
+            std::time::Instant::now().checked_add(std::time::Duration::from_secs(secs))
+        });
+        Self {
+            data: token.into(),
+            expires_at,
+        }
+    }
 }
 
 impl std::fmt::Debug for GoogleClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let access_token = self.access_token();
         f.debug_struct("GoogleClient")
-            .field("access_token", &self.access_token)
+            .field("access_token", &access_token)
             .field("refresh_token", &"[REDACTED]")
             .field(
                 "token_expiry",
-                &self.access_token.as_ref().unwrap().expires_on,
+                &access_token.as_ref().map(|token| token.expires_on),
             )
             .field("client_id", &self.client_credentials.client_id)
             .field("client_secret", &"[REDACTED]")
@@ -117,17 +259,110 @@ impl std::fmt::Debug for GoogleClient {
                 "refresh_handlers",
                 &format!("[{} handlers]", self.refresh_handlers.len()),
             )
+            .field("rate_limited", &self.current_rate_limiter().is_some())
+            .field("base_url", &self.base_url)
             .finish()
     }
 }
 
+impl Default for GoogleClient {
+    fn default() -> Self {
+        Self {
+            client_credentials: ClientCredentials::default(),
+            req_client: build_default_reqwest_client(),
+            auto_refresh_token: false,
+            refresh_handlers: Vec::new(),
+            rate_limiter: Arc::default(),
+            etag_cache: Arc::default(),
+            refresh_margin: chrono::Duration::seconds(DEFAULT_REFRESH_MARGIN_SECS),
+            default_calendar: DEFAULT_CALENDAR_ID.to_string(),
+            request_reason: None,
+            user_agent_suffix: None,
+            auth_user: None,
+            base_url: None,
+            service_account: None,
+            tokens: Arc::default(),
+        }
+    }
+}
+
+impl AccessToken {
+    /// The moment the token expires when it was obtained at `obtained_at`.
+    pub fn expiry_instant(&self, obtained_at: DateTime<Utc>) -> DateTime<Utc> {
+        obtained_at + chrono::Duration::seconds(self.expires_in)
+    }
+
+    /// The scopes the user granted that this crate knows, other scopes like `openid` are left out.
+    pub fn granted_scopes(&self) -> Vec<Scope> {
+        self.scope
+            .split_whitespace()
+            .filter_map(Scope::from_url)
+            .collect()
+    }
+
+    /// The granted scopes for showing to the user, as the label of each scope and whether it
+    /// allows changes, e.g. `("Calendar", true)` and `("Tasks", false)`.
+    pub fn granted_summary(&self) -> Vec<(&str, bool)> {
+        self.scope
+            .split_whitespace()
+            .filter_map(Scope::from_url)
+            .map(|scope| (scope.label(), scope.allows_write()))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl AccessToken {
+    /// A bearer token for tests that expires after the given number of seconds.
+    pub fn mock(expires_in_secs: i64) -> Self {
+        Self {
+            token_type: "Bearer".into(),
+            access_token: "atok".into(),
+            expires_in: expires_in_secs,
+            refresh_token: "rtok".into(),
+            refresh_token_expires_in: 3600,
+            scope: "scope".into(),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl GoogleClient {
+    /// A client for tests with dummy credentials and a token that's valid for years, so it never
+    /// contacts Google to refresh it. Point the builders at a mock server with `set_base_url`.
+    pub fn mock() -> Self {
+        Self::mock_with_expiry(10 * 365 * 24 * 60 * 60)
+    }
+
+    /// Same as `mock`, with a token that expires in `expires_in_secs`, e.g. to test the refresh
+    /// margin. A negative value results in a token without a monotonic expiry, which falls back
+    /// to the wall clock.
+    pub fn mock_with_expiry(expires_in_secs: i64) -> Self {
+        let credentials = ClientCredentials {
+            client_id: "cid".into(),
+            client_secret: "secret".into(),
+            redirect_uri: "https://example.com/cb".into(),
+            refresh_token: "rtok".into(),
+        };
+        Self::new(credentials, AccessToken::mock(expires_in_secs), false)
+    }
+}
+
+impl ClientTokenData {
+    /// Seconds until the access token expires, negative when it already expired.
+    pub fn seconds_until_expiry(&self) -> i64 {
+        (self.expires_on - Utc::now()).num_seconds()
+    }
+}
+
 impl From<AccessToken> for ClientTokenData {
     fn from(token: AccessToken) -> Self {
-        let now = chrono::Utc::now();
-        let new_expires_on = now + chrono::Duration::seconds(token.expires_in);
+        let now = Utc::now();
         Self {
+            expires_on: token.expiry_instant(now),
+            refresh_token_expires_on: (token.refresh_token_expires_in > 0)
+                .then(|| now + chrono::Duration::seconds(token.refresh_token_expires_in)),
             access_token: token.access_token,
-            expires_on: new_expires_on,
             refresh_token: token.refresh_token,
         }
     }
@@ -139,13 +374,40 @@ impl GoogleClient {
         access_token: AccessToken,
         auto_refresh_token: bool,
     ) -> Self {
-        let client = build_default_reqwest_client(&access_token.access_token);
+        let client = build_default_reqwest_client();
+        let tokens = TokenState {
+            token: std::sync::RwLock::new(Some(access_token.into())),
+            refresh_lock: tokio::sync::Mutex::default(),
+        };
         Self {
             client_credentials,
-            access_token: Some(access_token.into()),
             req_client: client,
             auto_refresh_token,
             refresh_handlers: Vec::new(),
+            rate_limiter: Arc::default(),
+            etag_cache: Arc::default(),
+            refresh_margin: chrono::Duration::seconds(DEFAULT_REFRESH_MARGIN_SECS),
+            default_calendar: DEFAULT_CALENDAR_ID.to_string(),
+            request_reason: None,
+            user_agent_suffix: None,
+            auth_user: None,
+            base_url: None,
+            service_account: None,
+            tokens: Arc::new(tokens),
+        }
+    }
+
+    /// Creates a client from a stored refresh token without a current access token,
+    /// e.g. after a restart. The access token is fetched on the first request,
+    /// afterwards `auto_refresh_token` decides whether it is refreshed when it expires.
+    pub fn from_refresh_token(
+        client_credentials: ClientCredentials,
+        auto_refresh_token: bool,
+    ) -> Self {
+        Self {
+            client_credentials,
+            auto_refresh_token,
+            ..Self::default()
         }
     }
 
@@ -156,11 +418,143 @@ impl GoogleClient {
         self.refresh_handlers.push(Arc::new(handler));
     }
 
-    pub async fn refresh_access_token_check(&mut self) -> Result<(), Error> {
-        if self.auto_refresh_token && !self.is_access_token_valid() {
-            self.update_access_token().await?;
+    /// Limits the requests of this client and its clones, also the ones made before, to
+    /// `requests_per_second`, using a `TokenBucket` that allows bursts of one second worth of
+    /// requests.
+    /// This helps staying under Google's per-user quota instead of running into 429 responses.
+    pub fn set_rate_limit(&mut self, requests_per_second: f64) {
+        self.set_rate_limiter(TokenBucket::new(requests_per_second));
+    }
+
+    /// Sets a custom rate limiter that is awaited before every request of this client and its
+    /// clones.
+    pub fn set_rate_limiter<R>(&mut self, rate_limiter: R)
+    where
+        R: RateLimiter + 'static,
+    {
+        self.replace_rate_limiter(Some(Arc::new(rate_limiter)));
+    }
+
+    /// Removes the rate limiter of this client and its clones.
+    pub fn remove_rate_limiter(&mut self) {
+        self.replace_rate_limiter(None);
+    }
+
+    fn replace_rate_limiter(&self, rate_limiter: Option<Arc<dyn RateLimiter>>) {
+        if let Ok(mut current) = self.rate_limiter.write() {
+            *current = rate_limiter;
         }
-        Ok(())
+    }
+
+    fn current_rate_limiter(&self) -> Option<Arc<dyn RateLimiter>> {
+        self.rate_limiter.read().ok()?.clone()
+    }
+
+    /// The ETag cache is always available, requests opt in with `conditional()`.
+    #[deprecated(note = "make a request conditional with `HeaderRequestTrait::conditional`")]
+    pub fn enable_etag_cache(&mut self) {}
+
+    /// Forgets the ETags of previous conditional requests, for this client and its clones.
+    #[deprecated(note = "requests are only conditional with `HeaderRequestTrait::conditional`")]
+    pub fn disable_etag_cache(&mut self) {
+        self.clear_etag_cache();
+    }
+
+    /// Forgets the ETags of previous conditional requests, for this client and its clones.
+    pub fn clear_etag_cache(&self) {
+        if let Ok(mut etags) = self.etag_cache.lock() {
+            etags.clear();
+        }
+    }
+
+    pub(crate) fn cached_etag(&self, key: &str) -> Option<String> {
+        self.etag_cache.lock().ok()?.get(key).cloned()
+    }
+
+    pub(crate) fn store_etag(&self, key: String, etag: String) {
+        if let Ok(mut etags) = self.etag_cache.lock() {
+            etags.insert(key, etag);
+        }
+    }
+
+    /// Refreshes the access token if auto refresh is enabled and the token is no longer valid.
+    /// Only one refresh runs at a time across clones of this client, callers that were waiting
+    /// on an in-flight refresh reuse its token instead of refreshing again.
+    pub async fn refresh_access_token_check(&self) -> Result<(), Error> {
+        if !self.auto_refresh_token || self.is_access_token_valid() {
+            return Ok(());
+        }
+
+        let _refreshing = self.tokens.refresh_lock.lock().await;
+        if self.is_access_token_valid() {
+            return Ok(());
+        }
+        self.fetch_access_token().await
+    }
+
+    /// Sets the calendar used by the `*_default` calendar requests like
+    /// `CalendarEventsClient::get_events_default`. Defaults to `DEFAULT_CALENDAR_ID`.
+    pub fn set_default_calendar(&mut self, calendar_id: &str) {
+        self.default_calendar = calendar_id.to_string();
+    }
+
+    pub fn default_calendar(&self) -> &str {
+        &self.default_calendar
+    }
+
+    /// Sets the `X-Goog-Request-Reason` header sent with every request of this client,
+    /// the reason shows up in the Cloud audit logs and helps correlating requests with Google.
+    pub fn set_request_reason(&mut self, reason: &str) {
+        self.request_reason = Some(reason.to_string());
+    }
+
+    pub fn request_reason(&self) -> Option<&str> {
+        self.request_reason.as_deref()
+    }
+
+    /// Appends an identifier of your app to the `User-Agent` of every request of this client,
+    /// e.g. `my-app/1.2` results in `google-workspace-apis-rs/1.2.0 my-app/1.2`.
+    /// This makes the traffic of your app recognizable in Google's diagnostics.
+    pub fn set_user_agent_suffix(&mut self, suffix: &str) {
+        self.user_agent_suffix = Some(suffix.to_string());
+    }
+
+    /// Sets the `X-Goog-AuthUser` header sent with every request of this client. When the
+    /// session covers multiple Google accounts the index selects the account, 0 being the first.
+    pub fn set_auth_user(&mut self, index: u32) {
+        self.auth_user = Some(index);
+    }
+
+    pub fn auth_user(&self) -> Option<u32> {
+        self.auth_user
+    }
+
+    /// Sends the requests of the typed clients to `base_url` instead of Google, keeping the
+    /// path and query, e.g. `http://127.0.0.1:1234` to point them at a mock server in tests.
+    /// The token endpoints aren't affected.
+    pub fn set_base_url(&mut self, base_url: &str) {
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+    }
+
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    /// Replaces the scheme and host of a Google API URL with the base URL, when one is set.
+    pub(crate) fn resolve_url(&self, url: &str) -> String {
+        let path = url
+            .strip_prefix("https://")
+            .and_then(|rest| rest.find('/').map(|index| &rest[index..]));
+        match (&self.base_url, path) {
+            (Some(base_url), Some(path)) => format!("{base_url}{path}"),
+            _ => url.to_string(),
+        }
+    }
+
+    /// Refresh the token by signing a new service account assertion instead of using the
+    /// refresh token of the credentials.
+    pub(crate) fn set_service_account(&mut self, service_account: ServiceAccount) {
+        self.service_account = Some(service_account);
     }
 
     pub fn enable_auto_refresh(&mut self) {
@@ -171,35 +565,494 @@ impl GoogleClient {
         self.auto_refresh_token = false;
     }
 
+    /// Sets how long before the actual expiry the access token is treated as invalid.
+    /// This makes the client refresh the token before a request could arrive at Google with
+    /// an expired token. Defaults to `DEFAULT_REFRESH_MARGIN_SECS`.
+    pub fn set_refresh_margin(&mut self, margin: chrono::Duration) {
+        self.refresh_margin = margin;
+    }
+
+    /// The current access token, shared with the clones of this client.
+    pub fn access_token(&self) -> Option<ClientTokenData> {
+        self.tokens
+            .token
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .map(|token| token.data.clone())
+    }
+
+    /// Returns true if the access token doesn't expire within the refresh margin.
     pub fn is_access_token_valid(&self) -> bool {
-        if let Some(token_data) = &self.access_token {
-            let now = chrono::Utc::now();
-            return now < token_data.expires_on;
+        self.tokens
+            .token
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .is_some_and(|token| self.is_token_valid(token))
+    }
+
+    /// Uses the monotonic clock when the token was obtained by this process, so a jump of the
+    /// system clock doesn't make the token look expired or valid. Tokens with an unknown
+    /// lifetime fall back to `expires_on`.
+    fn is_token_valid(&self, token: &StoredToken) -> bool {
+        match token.expires_at {
+            Some(expires_at) => {
+                let margin = self.refresh_margin.to_std().unwrap_or_default();
+                std::time::Instant::now()
+                    .checked_add(margin)
+                    .is_some_and(|deadline| deadline < expires_at)
+            }
+            None => chrono::Utc::now() + self.refresh_margin < token.data.expires_on,
+        }
+    }
+
+    /// Forces a refresh of the access token, regardless of whether the current one is valid.
+    pub async fn update_access_token(&self) -> Result<(), Error> {
+        let _refreshing = self.tokens.refresh_lock.lock().await;
+        self.fetch_access_token().await
+    }
+
+    /// Fetches the first access token, callers that were waiting on another request fetching it
+    /// reuse its token instead of fetching again.
+    async fn fetch_missing_access_token(&self) -> Result<(), Error> {
+        let _refreshing = self.tokens.refresh_lock.lock().await;
+        if self.access_token().is_some() {
+            return Ok(());
         }
-        false
+        self.fetch_access_token().await
+    }
+
+    /// Replaces the access token of this client and its clones, e.g. with a token your app
+    /// obtained or refreshed itself. The token refresh handlers aren't called.
+    pub fn set_access_token(&self, access_token: AccessToken) {
+        self.set_token(access_token.into());
+    }
+
+    /// Refreshes the token after Google rejected it, unless another clone already replaced
+    /// the rejected token with a valid one.
+    async fn refresh_rejected_token(&self, rejected_token: &str) -> Result<(), Error> {
+        let _refreshing = self.tokens.refresh_lock.lock().await;
+        let replaced = self
+            .tokens
+            .token
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .is_some_and(|token| {
+                token.data.access_token != rejected_token && self.is_token_valid(token)
+            });
+        if replaced {
+            return Ok(());
+        }
+        self.fetch_access_token().await
+    }
+
+    fn set_token(&self, mut new_token: StoredToken) {
+        let mut token = self
+            .tokens
+            .token
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // A refresh response usually doesn't repeat the refresh token expiry, keep the known
+        // expiry as long as the refresh token didn't change
+        let token_data = &mut new_token.data;
+        if token_data.refresh_token_expires_on.is_none() {
+            if let Some(current) = token
+                .as_ref()
+                .filter(|current| current.data.refresh_token == token_data.refresh_token)
+            {
+                token_data.refresh_token_expires_on = current.data.refresh_token_expires_on;
+            }
+        }
+        *token = Some(new_token);
+    }
+
+    /// The credentials with the latest refresh token, Google can rotate it on refresh.
+    fn current_credentials(&self) -> ClientCredentials {
+        let mut credentials = self.client_credentials.clone();
+        if let Some(token) = self
+            .access_token()
+            .filter(|token| !token.refresh_token.is_empty())
+        {
+            credentials.refresh_token = token.refresh_token;
+        }
+        credentials
+    }
+
+    /// Whether an access token can be fetched without user interaction.
+    fn can_fetch_access_token(&self) -> bool {
+        self.service_account.is_some() || !self.current_credentials().refresh_token.is_empty()
+    }
+
+    /// Fetches and stores a new token, callers hold the refresh lock.
+    async fn fetch_access_token(&self) -> Result<(), Error> {
+        let new_token = match &self.service_account {
+            Some(service_account) => service_account.fetch_access_token().await?,
+            None => refresh_acces_token(&self.current_credentials()).await?,
+        };
+        self.set_token(new_token.into());
+
+        if let Some(token) = self.access_token() {
+            for handler in &self.refresh_handlers {
+                handler.on_token_refresh(
+                    token.access_token.clone(),
+                    token.refresh_token.clone(),
+                    token.expires_on,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a request to an arbitrary Google API endpoint and returns the raw response.
+    /// The access token is refreshed first when needed, so this can be used for endpoints
+    /// that aren't covered by the typed clients yet.
+    ///
+    /// When auto refresh is enabled and Google responds with a 401 (e.g. the token was revoked),
+    /// the token is refreshed and the request is retried once. If the retry is rejected as well
+    /// a `GoogleApiError::Unauthorized` is returned. Without auto refresh a 401 is returned
+    /// as `GoogleApiError::Unauthorized` right away.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let res = client
+    ///     .request_raw(
+    ///         reqwest::Method::POST,
+    ///         "https://www.googleapis.com/calendar/v3/calendars/primary/events/quickAdd",
+    ///         &[("text", "Lunch tomorrow at noon")],
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// let event: Event = res.json().await?;
+    /// ```
+    ///
+    /// With the `tracing` feature enabled every request is wrapped in a `google_request` span
+    /// carrying the method, the URL path and the response status, and a debug event with the
+    /// latency is emitted once the request completes.
+    pub async fn request_raw<Q>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &Q,
+        body: Option<String>,
+    ) -> Result<reqwest::Response, Error>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.request_raw_with_content_type(method, url, query, body, JSON_CONTENT_TYPE)
+            .await
+    }
+
+    /// Same as `request_raw_with_content_type`, but sends the given headers as well,
+    /// e.g. `If-Match` for conditional updates.
+    pub async fn request_raw_with_headers<Q>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &Q,
+        body: Option<String>,
+        content_type: &str,
+        headers: &HeaderMap,
+    ) -> Result<reqwest::Response, Error>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let request = RawRequest {
+            method,
+            url,
+            body: body.map(String::into_bytes),
+            content_type,
+            headers,
+        };
+        self.send_instrumented(request, query).await
+    }
+
+    /// Same as `request_raw`, but the body is sent with the given `Content-Type` instead of
+    /// `application/json`, e.g. `message/rfc822` for raw MIME uploads or a multipart type for
+    /// batch requests.
+    pub async fn request_raw_with_content_type<Q>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &Q,
+        body: Option<String>,
+        content_type: &str,
+    ) -> Result<reqwest::Response, Error>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let request = RawRequest {
+            method,
+            url,
+            body: body.map(String::into_bytes),
+            content_type,
+            headers: &HeaderMap::new(),
+        };
+        self.send_instrumented(request, query).await
     }
 
-    pub async fn update_access_token(&mut self) -> Result<(), Error> {
-        let new_token = refresh_acces_token(&self.client_credentials).await?;
-        self.access_token = Some(new_token.clone().into());
-        let client = build_default_reqwest_client(&new_token.access_token);
-        self.req_client = client;
+    async fn send_instrumented<Q>(
+        &self,
+        request: RawRequest<'_>,
+        query: &Q,
+    ) -> Result<reqwest::Response, Error>
+    where
+        Q: Serialize + ?Sized,
+    {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
 
-        for handler in &mut self.refresh_handlers {
-            handler.on_token_refresh(
-                self.access_token.as_ref().unwrap().access_token.clone(),
-                self.access_token.as_ref().unwrap().refresh_token.clone(),
-                self.access_token.as_ref().unwrap().expires_on,
+            // Only the path is recorded, query parameters can contain personal data
+            let path = reqwest::Url::parse(request.url)
+                .map(|url| url.path().to_string())
+                .unwrap_or_default();
+            let span = tracing::debug_span!(
+                "google_request",
+                method = %request.method,
+                path = %path,
+                status = tracing::field::Empty,
             );
+            let started = std::time::Instant::now();
+            let res = self
+                .send_with_retry(request, query)
+                .instrument(span.clone())
+                .await;
+            let latency_ms = started.elapsed().as_millis() as u64;
+            match &res {
+                Ok(res) => {
+                    span.record("status", res.status().as_u16());
+                    tracing::debug!(parent: &span, latency_ms, "google request completed");
+                }
+                Err(err) => {
+                    tracing::debug!(parent: &span, latency_ms, error = %err, "google request failed");
+                }
+            }
+            res
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        self.send_with_retry(request, query).await
+    }
+
+    async fn send_with_retry<Q>(
+        &self,
+        request: RawRequest<'_>,
+        query: &Q,
+    ) -> Result<reqwest::Response, Error>
+    where
+        Q: Serialize + ?Sized,
+    {
+        if self.access_token().is_none() {
+            if !self.can_fetch_access_token() {
+                return Err(Error::Auth("client not authenticated".to_string()));
+            }
+            self.fetch_missing_access_token().await?;
+        }
+        self.refresh_access_token_check().await?;
+        self.acquire_rate_limit().await;
+        let res = self.build_request(&request, query)?.send().await?;
+        if res.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(res);
+        }
+        if !self.auto_refresh_token {
+            // Without auto refresh there's no retry, the rejected token is reported as is
+            return Err(GoogleApiError::from_response(res).await.into());
+        }
+
+        let rejected_token = self
+            .access_token()
+            .map(|token| token.access_token)
+            .unwrap_or_default();
+        self.refresh_rejected_token(&rejected_token).await?;
+        self.acquire_rate_limit().await;
+        let res = self.build_request(&request, query)?.send().await?;
+        if res.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(GoogleApiError::from_response(res).await.into());
+        }
+        Ok(res)
+    }
+
+    async fn acquire_rate_limit(&self) {
+        // Cloned out of the lock, which can't be held across the await
+        if let Some(rate_limiter) = self.current_rate_limiter() {
+            rate_limiter.acquire().await;
+        }
+    }
+
+    fn build_request<Q>(
+        &self,
+        request: &RawRequest<'_>,
+        query: &Q,
+    ) -> Result<reqwest::RequestBuilder, Error>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let mut headers = request.headers.clone();
+        let content_type = request.body.as_ref().map(|_| request.content_type);
+        self.complete_headers(&mut headers, content_type)?;
+        let mut req = self
+            .req_client
+            .request(request.method.clone(), request.url)
+            .query(query)
+            .headers(headers);
+        if let Some(token) = self.access_token() {
+            req = req.bearer_auth(token.access_token);
+        }
+        Ok(match &request.body {
+            Some(body) => req.body(body.clone()),
+            None => req,
+        })
+    }
+
+    /// Adds the client-wide headers like the request reason, and the content type when the
+    /// request has a body. Headers the request already sets take precedence.
+    /// Used for sending as well as for `Request::prepare`, so a dry run shows what is sent.
+    pub(crate) fn complete_headers(
+        &self,
+        headers: &mut HeaderMap,
+        content_type: Option<&str>,
+    ) -> Result<(), Error> {
+        fn insert_missing(
+            headers: &mut HeaderMap,
+            name: reqwest::header::HeaderName,
+            value: &str,
+        ) -> Result<(), Error> {
+            if !headers.contains_key(&name) {
+                let parsed = value.parse().map_err(|_| {
+                    Error::InvalidRequest(format!("Invalid header {name}: {value}"))
+                })?;
+                headers.insert(name, parsed);
+            }
+            Ok(())
+        }
+
+        if let Some(reason) = &self.request_reason {
+            let name = reqwest::header::HeaderName::from_static("x-goog-request-reason");
+            insert_missing(headers, name, reason)?;
+        }
+        if let Some(index) = self.auth_user {
+            let name = reqwest::header::HeaderName::from_static("x-goog-authuser");
+            insert_missing(headers, name, &index.to_string())?;
+        }
+        if let Some(suffix) = &self.user_agent_suffix {
+            let user_agent = format!("{DEFAULT_USER_AGENT} {suffix}");
+            insert_missing(headers, reqwest::header::USER_AGENT, &user_agent)?;
+        }
+        if let Some(content_type) = content_type {
+            insert_missing(headers, reqwest::header::CONTENT_TYPE, content_type)?;
         }
         Ok(())
     }
+
+    /// Sends a request composed by `Request`, with the token refresh and retry of `request_raw`.
+    pub(crate) async fn send_composed(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &QueryParams,
+        body: Option<Vec<u8>>,
+        headers: &HeaderMap,
+    ) -> Result<reqwest::Response, Error> {
+        let request = RawRequest {
+            method,
+            url,
+            body,
+            // The composed headers already hold the content type of the body
+            content_type: JSON_CONTENT_TYPE,
+            headers,
+        };
+        self.send_instrumented(request, query).await
+    }
+
+    /// Sends a GET request to an arbitrary Google API endpoint and deserializes the response.
+    /// Unsuccessful responses are returned as a `GoogleApiError`, an empty response body as None.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let settings: Option<serde_json::Value> = client
+    ///     .get_json(
+    ///         "https://www.googleapis.com/calendar/v3/users/me/settings",
+    ///         &[("maxResults", "10")],
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn get_json<T, Q>(&self, url: &str, query: &Q) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let res = self
+            .request_raw(reqwest::Method::GET, url, query, None)
+            .await?;
+        parse_json_response(res).await
+    }
+
+    /// Sends a POST request with a JSON body to an arbitrary Google API endpoint and
+    /// deserializes the response.
+    /// Unsuccessful responses are returned as a `GoogleApiError`, an empty response body as None.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let body = serde_json::json!({ "summary": "Team calendar" });
+    /// let calendar: Option<serde_json::Value> = client
+    ///     .post_json(
+    ///         "https://www.googleapis.com/calendar/v3/calendars",
+    ///         &body,
+    ///         &[("fields", "id,summary")],
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn post_json<B, T, Q>(
+        &self,
+        url: &str,
+        body: &B,
+        query: &Q,
+    ) -> Result<Option<T>, Error>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let body = serde_json::to_string(body)?;
+        let res = self
+            .request_raw(reqwest::Method::POST, url, query, Some(body))
+            .await?;
+        parse_json_response(res).await
+    }
+}
+
+async fn parse_json_response<T>(res: reqwest::Response) -> Result<Option<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    if !res.status().is_success() {
+        return Err(GoogleApiError::from_response(res).await.into());
+    }
+    let body = res.text().await?;
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&body)?))
 }
 
 pub trait TokenRefreshHandler: Send + Sync {
     fn on_token_refresh(&self, new_token: String, refresh_token: String, new_expiry: DateTime<Utc>);
 }
 
+/// Everything of a request that stays the same when it's retried with a refreshed token.
+struct RawRequest<'r> {
+    method: reqwest::Method,
+    url: &'r str,
+    body: Option<Vec<u8>>,
+    content_type: &'r str,
+    headers: &'r HeaderMap,
+}
+
 /// Helper function to convert a stored expiration date in UTC string format
 /// to the amount of seconds the token is still valid
 pub fn get_validity_token_secs(datetime_str: &str) -> i64 {
@@ -210,19 +1063,13 @@ pub fn get_validity_token_secs(datetime_str: &str) -> i64 {
     seconds_valid.num_seconds()
 }
 
-fn build_default_reqwest_client(token: &str) -> reqwest::Client {
+/// The access token is set per request, so the client and its connection pool outlive refreshes.
+fn build_default_reqwest_client() -> reqwest::Client {
     let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert(
-        reqwest::header::AUTHORIZATION,
-        format!("Bearer {token}").parse().unwrap(),
-    );
     headers.insert(reqwest::header::ACCEPT, "application/json".parse().unwrap());
-    headers.insert(
-        reqwest::header::CONTENT_TYPE,
-        "application/json".parse().unwrap(),
-    );
     reqwest::Client::builder()
         .default_headers(headers)
+        .user_agent(DEFAULT_USER_AGENT)
         .build()
         .expect("Failed to build reqwest client")
 }
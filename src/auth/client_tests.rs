@@ -0,0 +1,285 @@
+#![allow(clippy::unwrap_used)]
+
+use reqwest::Method;
+
+use super::client::{AccessToken, ClientCredentials, GoogleClient};
+use crate::error::Error;
+
+#[test]
+fn token_is_valid_until_the_refresh_margin() {
+    let client = GoogleClient::mock_with_expiry(3600);
+    assert!(client.is_access_token_valid());
+
+    // Expires within the default margin of 60 seconds
+    let mut client = GoogleClient::mock_with_expiry(30);
+    assert!(!client.is_access_token_valid());
+    client.set_refresh_margin(chrono::Duration::zero());
+    assert!(client.is_access_token_valid());
+}
+
+#[test]
+fn expired_token_is_invalid() {
+    let mut client = GoogleClient::mock_with_expiry(0);
+    client.set_refresh_margin(chrono::Duration::zero());
+    assert!(!client.is_access_token_valid());
+}
+
+#[test]
+fn negative_margin_doesnt_extend_a_monotonic_expiry() {
+    let mut client = GoogleClient::mock_with_expiry(0);
+    client.set_refresh_margin(chrono::Duration::seconds(-60));
+    assert!(!client.is_access_token_valid());
+}
+
+#[test]
+fn token_without_monotonic_expiry_uses_the_wall_clock() {
+    // Expired 30 seconds ago by the wall clock, only a negative margin keeps it valid
+    let mut client = GoogleClient::mock_with_expiry(-30);
+    assert!(!client.is_access_token_valid());
+    client.set_refresh_margin(chrono::Duration::seconds(-60));
+    assert!(client.is_access_token_valid());
+}
+
+#[test]
+fn replaced_token_gets_a_new_expiry() {
+    let mut client = GoogleClient::mock_with_expiry(0);
+    client.set_refresh_margin(chrono::Duration::zero());
+    let clone = client.clone();
+
+    client.set_access_token(super::client::AccessToken::mock(3600));
+    assert!(client.is_access_token_valid());
+    assert!(clone.is_access_token_valid());
+}
+
+#[test]
+fn client_credentials_from_installed_client_secret_json() {
+    let path = std::env::temp_dir().join("client_secret_test_installed.json");
+    std::fs::write(
+        &path,
+        r#"{"installed":{"client_id":"cid","client_secret":"secret","redirect_uris":["http://localhost"]}}"#,
+    )
+    .unwrap();
+
+    let creds = ClientCredentials::from_client_secret_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(creds.client_id, "cid");
+    assert_eq!(creds.client_secret, "secret");
+    assert_eq!(creds.redirect_uri, "http://localhost");
+    assert!(creds.refresh_token.is_empty());
+}
+
+#[tokio::test]
+async fn token_bucket_spaces_requests_after_the_burst() {
+    use crate::utils::rate_limit::{RateLimiter, TokenBucket};
+
+    let bucket = TokenBucket::with_capacity(20.0, 1.0);
+    let started = std::time::Instant::now();
+    for _ in 0..3 {
+        bucket.acquire().await;
+    }
+    // The first request uses the burst, the other two wait 50ms each
+    assert!(started.elapsed() >= std::time::Duration::from_millis(90));
+}
+
+#[tokio::test]
+async fn user_agent_identifies_the_crate_and_app() {
+    use crate::auth::client::DEFAULT_USER_AGENT;
+
+    let mut server = mockito::Server::new_async().await;
+    let default = server
+        .mock("GET", "/default")
+        .match_header("user-agent", DEFAULT_USER_AGENT)
+        .with_status(200)
+        .create_async()
+        .await;
+    let suffixed = server
+        .mock("GET", "/suffixed")
+        .match_header(
+            "user-agent",
+            format!("{DEFAULT_USER_AGENT} my-app/1.2").as_str(),
+        )
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let mut gc = GoogleClient::mock();
+    let no_query: &[(&str, &str)] = &[];
+    gc.request_raw(
+        Method::GET,
+        &format!("{}/default", server.url()),
+        no_query,
+        None,
+    )
+    .await
+    .unwrap();
+    gc.set_user_agent_suffix("my-app/1.2");
+    gc.request_raw(
+        Method::GET,
+        &format!("{}/suffixed", server.url()),
+        no_query,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert!(DEFAULT_USER_AGENT.starts_with("google-workspace-apis-rs/"));
+    default.assert_async().await;
+    suffixed.assert_async().await;
+}
+
+#[test]
+fn oauth_url_requires_an_absolute_redirect_uri() {
+    use crate::auth::{scopes::Scope, try_get_oauth_url, validate_redirect_uri};
+
+    let url =
+        try_get_oauth_url("cid", "https://example.com/cb", vec![Scope::CalendarEvents]).unwrap();
+    assert!(url.contains("redirect_uri=https%3A%2F%2Fexample.com%2Fcb"));
+
+    for invalid in ["", "  ", "/cb", "example.com/cb", "mailto:me@example.com"] {
+        assert!(
+            matches!(
+                validate_redirect_uri(invalid),
+                Err(Error::InvalidRequest(_))
+            ),
+            "{invalid:?} should be rejected"
+        );
+    }
+    assert!(matches!(
+        try_get_oauth_url("", "https://example.com/cb", vec![]),
+        Err(Error::InvalidRequest(_))
+    ));
+}
+
+#[test]
+fn oauth_url_encodes_the_query() {
+    use crate::auth::{get_oauth_url, scopes::Scope};
+
+    let url = get_oauth_url(
+        "id&x=1",
+        "https://example.com/cb?next=/home",
+        vec![Scope::CalendarEvents, Scope::Tasks],
+    );
+    assert_eq!(
+        url,
+        "https://accounts.google.com/o/oauth2/auth?client_id=id%26x%3D1\
+         &redirect_uri=https%3A%2F%2Fexample.com%2Fcb%3Fnext%3D%2Fhome&response_type=code\
+         &scope=https%3A%2F%2Fwww.googleapis.com%2Fauth%2Fcalendar.events+\
+         https%3A%2F%2Fwww.googleapis.com%2Fauth%2Ftasks&access_type=offline&prompt=consent"
+    );
+}
+
+#[test]
+fn incremental_oauth_url_includes_granted_scopes() {
+    use crate::auth::{get_incremental_oauth_url, get_oauth_url, scopes::Scope};
+
+    let url = get_incremental_oauth_url("cid", "https://example.com/cb", vec![Scope::Tasks]);
+    assert!(url.ends_with("&include_granted_scopes=true"));
+    assert!(
+        !get_oauth_url("cid", "https://example.com/cb", vec![Scope::Tasks])
+            .contains("include_granted_scopes")
+    );
+}
+
+#[test]
+fn oauth_url_options_control_prompt_and_login_hint() {
+    use crate::auth::{get_oauth_url, get_oauth_url_with_options, OAuthPrompt, OAuthUrlOptions};
+
+    assert!(get_oauth_url("cid", "https://example.com/cb", vec![]).ends_with("&prompt=consent"));
+
+    let options = OAuthUrlOptions {
+        prompt: None,
+        login_hint: Some("user@example.com".to_string()),
+        ..Default::default()
+    };
+    let url = get_oauth_url_with_options("cid", "https://example.com/cb", vec![], &options);
+    assert!(!url.contains("prompt="));
+    assert!(url.ends_with("&login_hint=user%40example.com"));
+
+    let options = OAuthUrlOptions {
+        login_hint: Some("a+b@example.com".to_string()),
+        ..Default::default()
+    };
+    let url = get_oauth_url_with_options("cid", "https://example.com/cb", vec![], &options);
+    assert!(url.ends_with("&login_hint=a%2Bb%40example.com"));
+
+    let options = OAuthUrlOptions {
+        prompt: Some(OAuthPrompt::None),
+        ..Default::default()
+    };
+    let url = get_oauth_url_with_options("cid", "https://example.com/cb", vec![], &options);
+    assert!(url.ends_with("&prompt=none"));
+
+    let options = OAuthUrlOptions {
+        hosted_domain: Some("example.com".to_string()),
+        ..Default::default()
+    };
+    let url = get_oauth_url_with_options("cid", "https://example.com/cb", vec![], &options);
+    assert!(url.ends_with("&prompt=consent&hd=example.com"));
+}
+
+#[test]
+fn granted_scopes_are_parsed_from_the_token() {
+    use crate::auth::scopes::Scope;
+
+    let mut token = AccessToken::mock(3600);
+    token.scope = "openid https://www.googleapis.com/auth/calendar \
+                   https://www.googleapis.com/auth/tasks.readonly https://mail.google.com/"
+        .to_string();
+
+    assert_eq!(
+        token.granted_scopes(),
+        [Scope::Calendar, Scope::TasksReadOnly, Scope::Mail]
+    );
+    assert_eq!(
+        token.granted_summary(),
+        [("Calendar", true), ("Tasks", false), ("Gmail", true)]
+    );
+    assert_eq!(
+        Scope::from_url(Scope::CalendarEventsPublicReadonly.as_str()),
+        Some(Scope::CalendarEventsPublicReadonly)
+    );
+}
+
+#[tokio::test]
+async fn rate_limiter_is_shared_with_earlier_clones() {
+    use crate::utils::rate_limit::RateLimiter;
+    use futures_util::future::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Counting(Arc<AtomicUsize>);
+    impl RateLimiter for Counting {
+        fn acquire(&self) -> BoxFuture<'_, ()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {})
+        }
+    }
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/limited")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+    let mut gc = GoogleClient::mock();
+    let earlier_clone = gc.clone();
+    let acquired = Arc::new(AtomicUsize::new(0));
+    gc.set_rate_limiter(Counting(acquired.clone()));
+
+    let url = format!("{}/limited", server.url());
+    let no_query: &[(&str, &str)] = &[];
+    earlier_clone
+        .request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap();
+    assert_eq!(acquired.load(Ordering::SeqCst), 1);
+
+    gc.remove_rate_limiter();
+    earlier_clone
+        .request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap();
+    assert_eq!(acquired.load(Ordering::SeqCst), 1);
+}
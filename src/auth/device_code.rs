@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use crate::error::Error;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{client::AccessToken, scopes::Scope};
+
+const DEVICE_CODE_URL: &str = "https://oauth2.googleapis.com/device/code";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// Seconds added to the polling interval when Google responds with `slow_down`.
+const SLOW_DOWN_INCREMENT_SECS: u64 = 5;
+
+fn default_interval() -> u64 {
+    5
+}
+
+/// Response of the device code endpoint, show the `user_code` and `verification_url` to the
+/// user and poll for the token with `poll_device_token`.
+#[derive(Debug, JsonSchema, Clone, Default, Serialize, Deserialize)]
+pub struct DeviceCodeResponse {
+    /// Code used to poll for the access token, this shouldn't be shown to the user.
+    pub device_code: String,
+    /// Code the user has to enter at the verification url.
+    pub user_code: String,
+    /// Url the user has to visit on another device to authorize the application.
+    pub verification_url: String,
+    /// Seconds until the device code and user code expire.
+    pub expires_in: i64,
+    /// Minimum amount of seconds to wait between polling requests.
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+/// Starts the device authorization flow for headless or CLI applications that can't handle a
+/// browser redirect. Google only allows a limited set of scopes for this flow and the OAuth
+/// client has to be of the "TVs and Limited Input devices" type.
+///
+/// # Example:
+/// ```
+/// let device_code = get_device_code(CLIENT_ID, vec![Scope::TasksReadOnly]).await?;
+/// println!(
+///     "Visit {} and enter the code {}",
+///     device_code.verification_url, device_code.user_code
+/// );
+/// let access_token = poll_device_token(CLIENT_ID, CLIENT_SECRET, &device_code).await?;
+/// ```
+pub async fn get_device_code(
+    client_id: &str,
+    scopes: Vec<Scope>,
+) -> Result<DeviceCodeResponse, Error> {
+    request_device_code(DEVICE_CODE_URL, client_id, scopes).await
+}
+
+pub(super) async fn request_device_code(
+    url: &str,
+    client_id: &str,
+    scopes: Vec<Scope>,
+) -> Result<DeviceCodeResponse, Error> {
+    let scope = scopes
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let params = [("client_id", client_id), ("scope", &scope)];
+
+    let response = reqwest::Client::new()
+        .post(url)
+        .form(&params)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(Error::Auth(format!(
+            "Failed to retrieve device code: {}",
+            response.status()
+        )));
+    }
+    Ok(response.json().await?)
+}
+
+/// Polls the token endpoint until the user authorized the device, denied access or the device
+/// code expired. The `interval` of the device code is honored and increased when Google asks
+/// to slow down.
+pub async fn poll_device_token(
+    client_id: &str,
+    client_secret: &str,
+    device_code: &DeviceCodeResponse,
+) -> Result<AccessToken, Error> {
+    poll_token_endpoint(TOKEN_URL, client_id, client_secret, device_code).await
+}
+
+pub(super) async fn poll_token_endpoint(
+    url: &str,
+    client_id: &str,
+    client_secret: &str,
+    device_code: &DeviceCodeResponse,
+) -> Result<AccessToken, Error> {
+    let params = [
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+        ("device_code", &device_code.device_code),
+        ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+    ];
+
+    let client = reqwest::Client::new();
+    let deadline = chrono::Utc::now() + chrono::Duration::seconds(device_code.expires_in);
+    let mut interval = device_code.interval;
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if chrono::Utc::now() >= deadline {
+            return Err(Error::Auth(
+                "Device code expired before it was authorized".to_string(),
+            ));
+        }
+
+        let response = client.post(url).form(&params).send().await?;
+        if response.status().is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        match body["error"].as_str() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval += SLOW_DOWN_INCREMENT_SECS,
+            Some(error) => {
+                return Err(Error::Auth(format!(
+                    "Failed to retrieve device token: {error}"
+                )))
+            }
+            None => {
+                return Err(Error::Auth(format!(
+                    "Failed to retrieve device token: {status}"
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+#![allow(clippy::unwrap_used)]
+
+use mockito::Matcher;
+
+use super::{
+    device_code::{poll_token_endpoint, request_device_code, DeviceCodeResponse},
+    scopes::Scope,
+};
+
+fn device_code(interval: u64) -> DeviceCodeResponse {
+    DeviceCodeResponse {
+        device_code: "device-1".to_string(),
+        user_code: "ABCD-EFGH".to_string(),
+        verification_url: "https://www.google.com/device".to_string(),
+        expires_in: 1800,
+        interval,
+    }
+}
+
+#[tokio::test]
+async fn device_code_is_requested_for_the_scopes() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/device/code")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("client_id".into(), "client".into()),
+            Matcher::UrlEncoded(
+                "scope".into(),
+                "https://www.googleapis.com/auth/tasks.readonly".into(),
+            ),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"{"device_code": "device-1", "user_code": "ABCD-EFGH",
+                "verification_url": "https://www.google.com/device", "expires_in": 1800}"#,
+        )
+        .create_async()
+        .await;
+
+    let url = format!("{}/device/code", server.url());
+    let response = request_device_code(&url, "client", vec![Scope::TasksReadOnly])
+        .await
+        .unwrap();
+    assert_eq!(response.user_code, "ABCD-EFGH");
+    // Google leaves out the interval when it's the default
+    assert_eq!(response.interval, 5);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn polling_continues_while_authorization_is_pending() {
+    let mut server = mockito::Server::new_async().await;
+    let pending = server
+        .mock("POST", "/token")
+        .with_status(428)
+        .with_body(r#"{"error": "authorization_pending"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let token = server
+        .mock("POST", "/token")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("device_code".into(), "device-1".into()),
+            Matcher::UrlEncoded(
+                "grant_type".into(),
+                "urn:ietf:params:oauth:grant-type:device_code".into(),
+            ),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"{"access_token": "device-token", "token_type": "Bearer", "expires_in": 3600}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let url = format!("{}/token", server.url());
+    let access_token = poll_token_endpoint(&url, "client", "secret", &device_code(0))
+        .await
+        .unwrap();
+    assert_eq!(access_token.access_token, "device-token");
+    pending.assert_async().await;
+    token.assert_async().await;
+}
+
+#[tokio::test]
+async fn denied_authorization_is_an_auth_error() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/token")
+        .with_status(403)
+        .with_body(r#"{"error": "access_denied"}"#)
+        .create_async()
+        .await;
+
+    let url = format!("{}/token", server.url());
+    let err = poll_token_endpoint(&url, "client", "secret", &device_code(0))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, crate::error::Error::Auth(message) if message.ends_with("access_denied"))
+    );
+}
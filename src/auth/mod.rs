@@ -1,9 +1,17 @@
-use anyhow::{anyhow, Error};
-use client::{AccessToken, ClientCredentials};
+use crate::error::Error;
+use client::{AccessToken, ClientCredentials, GoogleClient};
 use scopes::Scope;
 
 pub mod client;
+#[cfg(test)]
+mod client_tests;
+pub mod device_code;
+#[cfg(test)]
+mod device_code_tests;
 pub mod scopes;
+pub mod service_account;
+#[cfg(test)]
+mod service_account_tests;
 
 /// Helper function to generate the OAuth URL for Google authentication.
 /// # Example:
@@ -31,18 +39,166 @@ pub mod scopes;
 /// Google will redirect to the specified `redirect_uri` with the authorization code as a query
 /// parameter.
 pub fn get_oauth_url(client_id: &str, redirect_uri: &str, scopes: Vec<Scope>) -> String {
+    get_oauth_url_with_options(client_id, redirect_uri, scopes, &OAuthUrlOptions::default())
+}
+
+/// Same as `get_oauth_url`, but for incremental authorization: Google adds the requested scopes
+/// to the ones the user already granted, so e.g. Tasks access can be requested after Calendar
+/// without asking for Calendar again. The token from the exchange covers all granted scopes.
+pub fn get_incremental_oauth_url(
+    client_id: &str,
+    redirect_uri: &str,
+    scopes: Vec<Scope>,
+) -> String {
+    let options = OAuthUrlOptions {
+        include_granted_scopes: true,
+        ..Default::default()
+    };
+    get_oauth_url_with_options(client_id, redirect_uri, scopes, &options)
+}
+
+/// Same as `get_oauth_url` with control over the consent screen, e.g. to let a returning user
+/// pick their account instead of consenting again:
+/// ```
+/// use google_workspace_apis::auth::{get_oauth_url_with_options, OAuthPrompt, OAuthUrlOptions};
+/// use google_workspace_apis::auth::scopes::Scope;
+///
+/// let options = OAuthUrlOptions {
+///     prompt: Some(OAuthPrompt::SelectAccount),
+///     login_hint: Some("user@example.com".to_string()),
+///     ..Default::default()
+/// };
+/// let url = get_oauth_url_with_options(
+///     "client-id",
+///     "https://example.com/oauth2/redirect",
+///     vec![Scope::CalendarEvents],
+///     &options,
+/// );
+/// assert!(url.contains("prompt=select_account&login_hint=user%40example.com"));
+/// ```
+pub fn get_oauth_url_with_options(
+    client_id: &str,
+    redirect_uri: &str,
+    scopes: Vec<Scope>,
+    options: &OAuthUrlOptions,
+) -> String {
     let base_url = "https://accounts.google.com/o/oauth2/auth";
-    format!(
-        "{}?client_id={}&redirect_uri={}&response_type=code&scope={}&access_type=offline&prompt=consent",
-        base_url,
+    let scope = scopes
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let mut params = vec![
+        ("client_id", client_id),
+        ("redirect_uri", redirect_uri),
+        ("response_type", "code"),
+        ("scope", &scope),
+        ("access_type", "offline"),
+    ];
+    if let Some(prompt) = &options.prompt {
+        params.push(("prompt", prompt.as_str()));
+    }
+    if let Some(login_hint) = &options.login_hint {
+        params.push(("login_hint", login_hint));
+    }
+    if let Some(hosted_domain) = &options.hosted_domain {
+        params.push(("hd", hosted_domain));
+    }
+    if options.include_granted_scopes {
+        params.push(("include_granted_scopes", "true"));
+    }
+    reqwest::Url::parse_with_params(base_url, &params)
+        .expect("The OAuth base URL is valid")
+        .into()
+}
+
+/// Options of the OAuth URL, the defaults match `get_oauth_url`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthUrlOptions {
+    /// Which screens Google shows, None leaves it to Google. Defaults to `OAuthPrompt::Consent`,
+    /// which shows the consent screen every time and always returns a new refresh token.
+    pub prompt: Option<OAuthPrompt>,
+    /// Email address or account id that preselects the account on the sign in screen.
+    pub login_hint: Option<String>,
+    /// Restricts the sign in screen to accounts of this Workspace domain, e.g. `example.com`.
+    /// Google doesn't enforce it, check the `hd` claim of the ID token to be sure.
+    pub hosted_domain: Option<String>,
+    /// Adds the requested scopes to the ones the user already granted, see `get_incremental_oauth_url`.
+    pub include_granted_scopes: bool,
+}
+
+impl Default for OAuthUrlOptions {
+    fn default() -> Self {
+        Self {
+            prompt: Some(OAuthPrompt::Consent),
+            login_hint: None,
+            hosted_domain: None,
+            include_granted_scopes: false,
+        }
+    }
+}
+
+/// The `prompt` of the OAuth URL.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum OAuthPrompt {
+    /// Don't show any screen, fails when the user isn't signed in or hasn't consented yet.
+    None,
+    /// Always show the consent screen.
+    Consent,
+    /// Let the user pick one of their accounts.
+    SelectAccount,
+}
+
+impl OAuthPrompt {
+    pub fn as_str(&self) -> &str {
+        match self {
+            OAuthPrompt::None => "none",
+            OAuthPrompt::Consent => "consent",
+            OAuthPrompt::SelectAccount => "select_account",
+        }
+    }
+}
+
+/// Same as `get_oauth_url`, but validates the `redirect_uri` first. An empty or relative URI,
+/// or one that isn't http(s), fails with `Error::InvalidRequest` instead of producing a consent
+/// URL that Google rejects with a redirect URI mismatch.
+pub fn try_get_oauth_url(
+    client_id: &str,
+    redirect_uri: &str,
+    scopes: Vec<Scope>,
+) -> Result<String, Error> {
+    if client_id.is_empty() {
+        return Err(Error::InvalidRequest("The client id is empty".to_string()));
+    }
+    validate_redirect_uri(redirect_uri)?;
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
         client_id,
         redirect_uri,
-        scopes
-            .iter()
-            .map(|s| s.as_str())
-            .collect::<Vec<&str>>()
-            .join(" ")
-    )
+        scopes = scopes.len(),
+        "generating google oauth url"
+    );
+    Ok(get_oauth_url(client_id, redirect_uri, scopes))
+}
+
+/// Checks that the redirect URI is an absolute http(s) URL with a host. It still has to match
+/// one of the redirect URIs registered for the client in the Google Cloud console exactly.
+pub fn validate_redirect_uri(redirect_uri: &str) -> Result<(), Error> {
+    if redirect_uri.trim().is_empty() {
+        return Err(Error::InvalidRequest(
+            "The redirect URI is empty".to_string(),
+        ));
+    }
+    let url = reqwest::Url::parse(redirect_uri).map_err(|err| {
+        Error::InvalidRequest(format!("Invalid redirect URI {redirect_uri}: {err}"))
+    })?;
+    if !matches!(url.scheme(), "http" | "https") || !url.has_host() {
+        return Err(Error::InvalidRequest(format!(
+            "The redirect URI {redirect_uri} has to be an absolute http(s) URL"
+        )));
+    }
+    Ok(())
 }
 
 /// Helper function to retrieve an access token from Google using the authorization code.
@@ -112,39 +268,53 @@ pub async fn get_acces_token(
     match res {
         Ok(response) => {
             if response.status().is_success() {
-                let json: serde_json::Value = response.json().await?;
-                Ok(
-                    serde_json::from_value(json.clone()).unwrap_or_else(|_| AccessToken {
-                        token_type: json["token_type"].as_str().unwrap_or_default().to_string(),
-                        access_token: json["access_token"]
-                            .as_str()
-                            .unwrap_or_default()
-                            .to_string(),
-                        expires_in: json["expires_in"].as_i64().unwrap_or(0),
-                        refresh_token: json["refresh_token"]
-                            .as_str()
-                            .unwrap_or_default()
-                            .to_string(),
-                        refresh_token_expires_in: json["x_refresh_token_expires_in"]
-                            .as_i64()
-                            .unwrap_or(0),
-                        scope: json["scope"].as_str().unwrap_or_default().to_string(),
-                    }),
-                )
+                Ok(response.json().await?)
             } else {
-                Err(anyhow::anyhow!(
+                Err(Error::Auth(format!(
                     "Failed to retrieve access token: {}",
                     response.status()
-                ))
+                )))
             }
         }
-        Err(e) => Err(anyhow::anyhow!(e)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Exchanges the authorization code from the OAuth redirect and builds a client with the token.
+/// The refresh token Google returns is copied into the credentials, when Google doesn't return one
+/// the refresh token already in `client_credentials` is kept.
+pub async fn complete_oauth(
+    code: &str,
+    client_credentials: &ClientCredentials,
+    auto_refresh_token: bool,
+) -> Result<GoogleClient, Error> {
+    let mut access_token = get_acces_token(
+        code,
+        &client_credentials.client_secret,
+        &client_credentials.client_id,
+        &client_credentials.redirect_uri,
+    )
+    .await?;
+    let mut credentials = client_credentials.clone();
+    if access_token.refresh_token.is_empty() {
+        access_token.refresh_token = credentials.refresh_token.clone();
+    } else {
+        credentials.refresh_token = access_token.refresh_token.clone();
     }
+    Ok(GoogleClient::new(
+        credentials,
+        access_token,
+        auto_refresh_token,
+    ))
 }
 
+/// Helper function to retrieve a new access token using the refresh token in the credentials.
+/// The full token response is returned, including `expires_in`, so the expiry of the new token
+/// can be computed. Google usually doesn't return a new refresh token when refreshing,
+/// in that case the refresh token from the credentials is kept.
 pub async fn refresh_acces_token(
     client_credentials: &ClientCredentials,
-) -> Result<AccessToken, anyhow::Error> {
+) -> Result<AccessToken, Error> {
     let url = "https://oauth2.googleapis.com/token";
     let params = [
         ("client_id", client_credentials.client_id.clone()),
@@ -159,23 +329,18 @@ pub async fn refresh_acces_token(
     match res.await {
         Ok(response) => {
             if response.status().is_success() {
-                let json: serde_json::Value = response.json().await?;
-                let token = AccessToken {
-                    token_type: json["token_type"].as_str().unwrap_or_default().to_string(),
-                    access_token: json["access_token"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                    expires_in: json["expires_in"].as_i64().unwrap_or(0),
-                    refresh_token: client_credentials.refresh_token.clone(),
-                    refresh_token_expires_in: 0,
-                    scope: json["scope"].as_str().unwrap_or_default().to_string(),
-                };
+                let mut token: AccessToken = response.json().await?;
+                if token.refresh_token.is_empty() {
+                    token.refresh_token = client_credentials.refresh_token.clone();
+                }
                 Ok(token)
             } else {
-                Err(anyhow!("Failed to refresh token: {}", response.status()))
+                Err(Error::Auth(format!(
+                    "Failed to refresh token: {}",
+                    response.status()
+                )))
             }
         }
-        Err(e) => Err(anyhow!("Request error: {e}")),
+        Err(e) => Err(e.into()),
     }
 }
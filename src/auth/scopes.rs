@@ -1,3 +1,5 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Scope {
     //Calendar
     Calendar,
@@ -36,7 +38,7 @@ impl Scope {
                 "https://www.googleapis.com/auth/calendar.events.owned.readonly"
             }
             Scope::CalendarEventsPublicReadonly => {
-                "https://www.googleapis.com/auth/calendar.readonly"
+                "https://www.googleapis.com/auth/calendar.events.public.readonly"
             }
             Scope::CalendarReadOnly => "https://www.googleapis.com/auth/calendar.readonly",
             Scope::CalendarEvents => "https://www.googleapis.com/auth/calendar.events",
@@ -50,4 +52,61 @@ impl Scope {
             Scope::MailMetadata => "https://www.googleapis.com/auth/gmail.metadata",
         }
     }
+
+    /// All scopes this crate knows, in declaration order.
+    const ALL: [Scope; 15] = [
+        Scope::Calendar,
+        Scope::CalendarEvents,
+        Scope::CalendarEventsReadonly,
+        Scope::CalendarReadOnly,
+        Scope::CalendarAppCreated,
+        Scope::CalendarEventsFreeBusy,
+        Scope::CalendarEventsOwned,
+        Scope::CalendarEventsOwnedReadonly,
+        Scope::CalendarEventsPublicReadonly,
+        Scope::TasksReadOnly,
+        Scope::Tasks,
+        Scope::Mail,
+        Scope::MailModify,
+        Scope::MailReadonly,
+        Scope::MailMetadata,
+    ];
+
+    /// The scope of a URL as returned in the `scope` of a token, `None` for scopes this crate
+    /// doesn't know like `openid` or the Drive scopes. A trailing slash is ignored.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url.trim_end_matches('/');
+        Self::ALL.into_iter().find(|scope| scope.as_str() == url)
+    }
+
+    /// A short name of what the scope gives access to, to show to users.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Scope::Calendar | Scope::CalendarReadOnly => "Calendar",
+            Scope::CalendarEvents | Scope::CalendarEventsReadonly => "Calendar events",
+            Scope::CalendarAppCreated => "Calendars created by this app",
+            Scope::CalendarEventsFreeBusy => "Calendar free/busy",
+            Scope::CalendarEventsOwned | Scope::CalendarEventsOwnedReadonly => {
+                "Owned calendar events"
+            }
+            Scope::CalendarEventsPublicReadonly => "Public calendar events",
+            Scope::Tasks | Scope::TasksReadOnly => "Tasks",
+            Scope::Mail | Scope::MailModify | Scope::MailReadonly => "Gmail",
+            Scope::MailMetadata => "Gmail metadata",
+        }
+    }
+
+    /// Whether the scope allows changes, as opposed to only reading.
+    pub fn allows_write(&self) -> bool {
+        matches!(
+            self,
+            Scope::Calendar
+                | Scope::CalendarEvents
+                | Scope::CalendarAppCreated
+                | Scope::CalendarEventsOwned
+                | Scope::Tasks
+                | Scope::Mail
+                | Scope::MailModify
+        )
+    }
 }
@@ -0,0 +1,194 @@
+use crate::error::Error;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::{
+    rand::SystemRandom,
+    signature::{RsaKeyPair, RSA_PKCS1_SHA256},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    client::{AccessToken, ClientCredentials, GoogleClient},
+    scopes::Scope,
+};
+
+/// Lifetime of the signed JWT assertion, Google accepts at most one hour.
+const ASSERTION_LIFETIME_SECS: i64 = 3600;
+
+fn default_token_uri() -> String {
+    "https://oauth2.googleapis.com/token".to_string()
+}
+
+/// The service account key JSON as downloaded from the Google Cloud Console.
+#[derive(Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    pub client_email: String,
+    #[serde(default)]
+    pub private_key_id: String,
+    /// PEM encoded PKCS#8 RSA private key.
+    pub private_key: String,
+    #[serde(default = "default_token_uri")]
+    pub token_uri: String,
+}
+
+impl std::fmt::Debug for ServiceAccountKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceAccountKey")
+            .field("client_email", &self.client_email)
+            .field("private_key_id", &self.private_key_id)
+            .field("private_key", &"[REDACTED]")
+            .field("token_uri", &self.token_uri)
+            .finish()
+    }
+}
+
+impl ServiceAccountKey {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn from_json_file(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[derive(Serialize)]
+struct JwtHeader<'a> {
+    alg: &'a str,
+    typ: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    kid: &'a str,
+}
+
+#[derive(Serialize)]
+struct JwtClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<&'a str>,
+}
+
+/// Server to server authentication with a service account key.
+/// Set a subject to impersonate a user of the Workspace domain, this requires domain-wide
+/// delegation to be enabled for the service account.
+///
+/// # Example:
+/// ```
+/// let key = ServiceAccountKey::from_json_file("service_account.json")?;
+/// let client = ServiceAccount::new(key, vec![Scope::Calendar])
+///     .with_subject("user@example.com")
+///     .into_client()
+///     .await?;
+///
+/// let events = CalendarEventsClient::new(&client)
+///     .get_events("primary")
+///     .request()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ServiceAccount {
+    key: ServiceAccountKey,
+    scopes: Vec<String>,
+    subject: Option<String>,
+}
+
+impl ServiceAccount {
+    pub fn new(key: ServiceAccountKey, scopes: Vec<Scope>) -> Self {
+        Self {
+            key,
+            scopes: scopes.iter().map(|s| s.as_str().to_string()).collect(),
+            subject: None,
+        }
+    }
+
+    /// Impersonate the given user using domain-wide delegation.
+    pub fn with_subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    /// Exchanges a signed JWT assertion for an access token.
+    /// Service accounts don't receive a refresh token, a new assertion is signed instead.
+    pub async fn fetch_access_token(&self) -> Result<AccessToken, Error> {
+        let assertion = self.sign_assertion(chrono::Utc::now().timestamp())?;
+        let params = [
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", &assertion),
+        ];
+
+        let response = reqwest::Client::new()
+            .post(&self.key.token_uri)
+            .form(&params)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(Error::Auth(format!(
+                "Failed to retrieve service account token: {}",
+                response.status()
+            )));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Fetches an access token and creates a client that signs a new assertion whenever the
+    /// token expires.
+    pub async fn into_client(self) -> Result<GoogleClient, Error> {
+        let token = self.fetch_access_token().await?;
+        let mut client = GoogleClient::new(ClientCredentials::default(), token, true);
+        client.set_service_account(self);
+        Ok(client)
+    }
+
+    pub(super) fn sign_assertion(&self, issued_at: i64) -> Result<String, Error> {
+        let header = JwtHeader {
+            alg: "RS256",
+            typ: "JWT",
+            kid: &self.key.private_key_id,
+        };
+        let scope = self.scopes.join(" ");
+        let claims = JwtClaims {
+            iss: &self.key.client_email,
+            scope: &scope,
+            aud: &self.key.token_uri,
+            iat: issued_at,
+            exp: issued_at + ASSERTION_LIFETIME_SECS,
+            sub: self.subject.as_deref(),
+        };
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?),
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims)?)
+        );
+
+        let key_pair = RsaKeyPair::from_pkcs8(&pem_to_der(&self.key.private_key)?)
+            .map_err(|e| Error::Auth(format!("Invalid service account private key: {e}")))?;
+        let mut signature = vec![0; key_pair.public().modulus_len()];
+        key_pair
+            .sign(
+                &RSA_PKCS1_SHA256,
+                &SystemRandom::new(),
+                signing_input.as_bytes(),
+                &mut signature,
+            )
+            .map_err(|_| Error::Auth("Failed to sign the service account assertion".to_string()))?;
+
+        Ok(format!(
+            "{signing_input}.{}",
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+}
+
+/// Decodes the PEM key, key files saved on Windows end their lines with `\r\n`.
+pub(super) fn pem_to_der(pem: &str) -> Result<Vec<u8>, Error> {
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .flat_map(|line| line.chars().filter(|c| *c != '\r'))
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(body.trim())
+        .map_err(|e| Error::Auth(format!("Invalid service account private key: {e}")))
+}
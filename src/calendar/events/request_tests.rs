@@ -1,19 +1,24 @@
 #![allow(clippy::unwrap_used)]
 
 use crate::{
-    auth::client::{get_validity_token_secs, AccessToken, ClientCredentials, GoogleClient},
+    auth::client::{
+        get_validity_token_secs, AccessToken, ClientCredentials, ClientTokenData, GoogleClient,
+    },
     calendar::{
         events::{
             requests::EventRequest,
-            types::{EventAttendee, EventList},
+            types::{Channel, Event, EventAttendee, EventList, WorkingLocationProperties},
+        },
+        prelude::{
+            EventColor, EventOrderBy, EventStatus, EventType, FetchedEventStatus, SendUpdates,
+            Visibility, When,
         },
-        prelude::{EventOrderBy, EventType},
     },
-    utils::request::TimeRequestTrait,
+    utils::request::{PaginationRequestTrait, RawParamRequestTrait, TimeRequestTrait},
 };
 
 use super::{requests::CalendarEventsClient, types::EventDateTime};
-use anyhow::Error;
+use crate::error::{Error, GoogleApiError};
 use chrono::{Duration, Utc};
 use reqwest::Method;
 
@@ -27,23 +32,12 @@ fn dummy_creds() -> ClientCredentials {
 }
 
 fn dummy_access(expires_in_secs: i64) -> AccessToken {
-    AccessToken {
-        token_type: "Bearer".into(),
-        access_token: "atok".into(),
-        expires_in: expires_in_secs,
-        refresh_token: "rtok".into(),
-        refresh_token_expires_in: 3600,
-        scope: "scope".into(),
-    }
+    AccessToken::mock(expires_in_secs)
 }
 
 fn dummy_google_client_valid() -> GoogleClient {
     // long validity -> won't try to refresh during tests
-    GoogleClient::new(
-        dummy_creds(),
-        dummy_access(60 * 60),
-        /*auto_refresh_token=*/ false,
-    )
+    GoogleClient::mock()
 }
 
 fn sample_dt(date: &str) -> EventDateTime {
@@ -70,6 +64,13 @@ fn event_type_as_str() {
     assert_eq!(EventType::WorkingLocation.as_str(), "workingLocation");
 }
 
+#[test]
+fn event_color_as_str() {
+    assert_eq!(EventColor::Lavender.as_str(), "1");
+    assert_eq!(EventColor::Banana.as_str(), "5");
+    assert_eq!(EventColor::Tomato.as_str(), "11");
+}
+
 #[test]
 fn get_validity_token_secs_works_for_past_and_future() {
     let future = (Utc::now() + Duration::seconds(120)).to_rfc3339();
@@ -79,23 +80,85 @@ fn get_validity_token_secs_works_for_past_and_future() {
     assert!(get_validity_token_secs(&past) < 0);
 }
 
+#[test]
+fn access_token_deserializes_token_endpoint_response() {
+    let token: AccessToken = serde_json::from_str(
+        r#"{
+            "access_token": "atok",
+            "expires_in": 3599,
+            "refresh_token": "rtok",
+            "x_refresh_token_expires_in": 604799,
+            "scope": "https://www.googleapis.com/auth/calendar",
+            "token_type": "Bearer"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(token.access_token, "atok");
+    assert_eq!(token.expires_in, 3599);
+    assert_eq!(token.refresh_token_expires_in, 604799);
+
+    assert!(serde_json::from_str::<AccessToken>(r#"{"expires_in":"soon"}"#).is_err());
+}
+
+#[test]
+fn access_token_expiry_helpers() {
+    let obtained_at = Utc::now();
+    let token = dummy_access(120);
+    assert_eq!(
+        token.expiry_instant(obtained_at),
+        obtained_at + Duration::seconds(120)
+    );
+
+    let gc = GoogleClient::new(dummy_creds(), token, false);
+    let token_data = gc.access_token().unwrap();
+    let seconds = token_data.seconds_until_expiry();
+    assert!((118..=120).contains(&seconds));
+    // dummy_access reports a refresh token lifetime of an hour
+    assert!(token_data.refresh_token_expires_on.unwrap() > Utc::now() + Duration::minutes(59));
+
+    let mut token = dummy_access(120);
+    token.refresh_token_expires_in = 0;
+    let token_data = ClientTokenData::from(token);
+    assert_eq!(token_data.refresh_token_expires_on, None);
+}
+
 #[test]
 fn google_client_validity_flag() {
     let gc = dummy_google_client_valid();
     assert!(gc.is_access_token_valid());
 }
 
+#[test]
+fn access_token_within_refresh_margin_is_invalid() {
+    let mut gc = GoogleClient::new(dummy_creds(), dummy_access(30), false);
+    assert!(!gc.is_access_token_valid());
+
+    gc.set_refresh_margin(Duration::zero());
+    assert!(gc.is_access_token_valid());
+}
+
+#[test]
+fn access_token_defaults_missing_expires_in() {
+    let token: AccessToken =
+        serde_json::from_str(r#"{"access_token":"atok","token_type":"Bearer"}"#).unwrap();
+    assert_eq!(token.expires_in, 3600);
+
+    let gc = GoogleClient::new(dummy_creds(), token, true);
+    assert!(gc.is_access_token_valid());
+}
+
 #[test]
 fn get_events_sets_url_method_and_params() {
-    let mut gc = dummy_google_client_valid();
+    let gc = dummy_google_client_valid();
 
-    let builder = CalendarEventsClient::new(&mut gc)
+    let builder = CalendarEventsClient::new(&gc)
         .get_events("primary")
         .single_events(true)
         .event_type(EventType::Birthday)
         .order_by(EventOrderBy::StartTime)
         .max_attendees(5)
         .show_hidden_invitations(true)
+        .always_include_email(true)
         .query("hello world");
 
     // We’re in the same module (sibling tests.rs), so we can examine private fields
@@ -114,17 +177,21 @@ fn get_events_sets_url_method_and_params() {
         p.get("showHiddenInvitations").map(String::as_str),
         Some("true")
     );
+    assert_eq!(
+        p.get("alwaysIncludeEmail").map(String::as_str),
+        Some("true")
+    );
     assert_eq!(p.get("q").map(String::as_str), Some("hello world"));
 }
 
 #[test]
 fn time_filters_are_serialized_as_rfc3339() {
-    let mut gc = dummy_google_client_valid();
+    let gc = dummy_google_client_valid();
 
     let now = Utc::now();
     let later = now + Duration::days(1);
 
-    let builder = CalendarEventsClient::new(&mut gc)
+    let builder = CalendarEventsClient::new(&gc)
         .get_events("primary")
         .time_min(now)
         .time_max(later);
@@ -136,7 +203,7 @@ fn time_filters_are_serialized_as_rfc3339() {
 
 #[test]
 fn insert_event_builds_body_and_setters_apply() {
-    let mut gc = dummy_google_client_valid();
+    let gc = dummy_google_client_valid();
 
     let start = sample_dt("2025-07-28");
     let end = sample_dt("2025-07-28");
@@ -156,13 +223,13 @@ fn insert_event_builds_body_and_setters_apply() {
         }
     }];
 
-    let builder = CalendarEventsClient::new(&mut gc)
+    let builder = CalendarEventsClient::new(&gc)
         .insert_event("cal_123", start.clone(), end.clone())
         .set_summary("My Summary")
         .set_description("Desc")
         .set_location("Somewhere")
         .set_attendees(attendees.clone())
-        .set_type(EventType::Default)
+        .set_event_type(EventType::Default)
         .set_color_id("5")
         .set_recurrence(vec!["RRULE:FREQ=DAILY".into()]);
 
@@ -192,12 +259,12 @@ fn insert_event_builds_body_and_setters_apply() {
 
 #[test]
 fn patch_event_setters_apply() {
-    let mut gc = dummy_google_client_valid();
+    let gc = dummy_google_client_valid();
 
     let new_start = sample_dt("2026-02-01");
     let new_end = sample_dt("2026-02-02");
 
-    let builder = CalendarEventsClient::new(&mut gc)
+    let builder = CalendarEventsClient::new(&gc)
         .patch_event("primary", "evt_42")
         .set_summary("New title")
         .set_description("New desc")
@@ -210,12 +277,12 @@ fn patch_event_setters_apply() {
         .set_id("new-id")
         .set_recurrence(vec!["RRULE:FREQ=WEEKLY".into()])
         .set_sequence(7)
-        .set_status("tentative")
+        .set_event_status(EventStatus::Tentative)
         .set_transparancy("opaque")
-        .set_visibility("private")
+        .set_visibility(Visibility::Private)
         .set_start(new_start.clone())
         .set_end(new_end.clone())
-        .set_send_updates("all")
+        .set_send_updates(SendUpdates::All)
         .set_conference_data_version(1)
         .support_attachments(true)
         .set_max_attendees(3);
@@ -265,12 +332,12 @@ fn patch_event_setters_apply() {
 
 #[test]
 fn serialized_event_body_matches_expected_format() {
-    let mut gc = dummy_google_client_valid();
+    let gc = dummy_google_client_valid();
 
     let new_start = sample_dt("2026-02-01");
     let new_end = sample_dt("2026-02-02");
 
-    let builder = CalendarEventsClient::new(&mut gc)
+    let builder = CalendarEventsClient::new(&gc)
         .patch_event("primary", "evt_42")
         .set_summary("New title")
         .set_description("New desc")
@@ -293,14 +360,1169 @@ fn serialized_event_body_matches_expected_format() {
     assert_eq!(parsed["end"]["date"], "2026-02-02");
 }
 
+#[test]
+fn serialized_insert_body_contains_description() {
+    let gc = dummy_google_client_valid();
+
+    let builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_summary("Title")
+        .set_description("Desc");
+
+    let event = builder.event.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::to_value(event).unwrap();
+
+    assert_eq!(parsed["description"], "Desc");
+    assert_eq!(parsed["summary"], "Title");
+    assert_eq!(parsed["start"]["date"], "2026-02-01");
+    assert_eq!(parsed["end"]["date"], "2026-02-02");
+}
+
+#[test]
+fn serialized_insert_body_uses_camel_case_guest_permissions() {
+    let gc = dummy_google_client_valid();
+
+    let builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_guests_can_invite_others(false)
+        .set_guests_can_modify(false)
+        .set_guests_can_see_other_guests(false);
+
+    let event = builder.event.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::to_value(event).unwrap();
+
+    assert_eq!(parsed["guestsCanInviteOthers"], false);
+    assert_eq!(parsed["guestsCanModify"], false);
+    assert_eq!(parsed["guestsCanSeeOtherGuests"], false);
+    assert!(parsed.get("guests_can_see_other_guests").is_none());
+}
+
+#[test]
+fn when_converts_to_a_single_event_date_time_field() {
+    let day = chrono::NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+    assert_eq!(EventDateTime::from(When::AllDay(day)), sample_dt("2026-02-01"));
+
+    let now = Utc::now();
+    let timed = EventDateTime::from(When::At(now, Some("Europe/Amsterdam".into())));
+    assert_eq!(timed.date, None);
+    assert_eq!(timed.date_time, Some(now));
+    assert_eq!(timed.time_zone.as_deref(), Some("Europe/Amsterdam"));
+}
+
+#[test]
+fn when_converts_to_the_start_and_end_of_an_event() {
+    let day = chrono::NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+    let (start, end) = When::AllDay(day).into();
+    assert_eq!(start, sample_dt("2026-02-01"));
+    assert_eq!(end, sample_dt("2026-02-02"));
+
+    let now = Utc::now();
+    let later = now + Duration::hours(2);
+    let zone = Some("Europe/Amsterdam".to_string());
+    let (start, end): (EventDateTime, EventDateTime) = When::Span(now..later, zone).into();
+    assert_eq!(start.date, None);
+    assert_eq!(start.date_time, Some(now));
+    assert_eq!(end.date_time, Some(later));
+    assert_eq!(end.time_zone.as_deref(), Some("Europe/Amsterdam"));
+
+    let (start, end): (EventDateTime, EventDateTime) = When::At(now, None).into();
+    assert_eq!(start, end);
+    assert_eq!(
+        EventDateTime::from(When::Span(now..later, None)).date_time,
+        Some(now)
+    );
+}
+
 #[tokio::test]
 async fn make_request_unsupported_method_errors() {
-    let mut gc = dummy_google_client_valid();
+    let gc = dummy_google_client_valid();
     // Start from any mode; we'll get EventListMode by calling get_events
-    let mut client = CalendarEventsClient::new(&mut gc).get_events("primary");
+    let mut client = CalendarEventsClient::new(&gc).get_events("primary");
     // Force an unsupported method to exercise the error path without any network I/O
     client.request.method = Method::DELETE;
 
     let res: Result<Option<EventList>, Error> = client.make_request().await;
     assert!(res.is_err());
 }
+
+#[tokio::test]
+async fn default_client_is_debuggable_and_refuses_requests() {
+    let gc = GoogleClient::default();
+    assert!(format!("{gc:?}").contains("token_expiry: None"));
+
+    let res = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .request()
+        .await;
+    assert!(
+        matches!(res.unwrap_err(), Error::Auth(message) if message == "client not authenticated")
+    );
+}
+
+#[test]
+fn event_date_time_normalizes_all_day_and_timed_events() {
+    let now = Utc::now();
+    let timed: EventDateTime = When::At(now, None).into();
+    assert_eq!(timed.to_utc(), Some(now));
+
+    // Without a zone the calendar's zone applies, which isn't known here
+    let all_day = sample_dt("2025-07-28");
+    assert_eq!(all_day.to_utc(), None);
+    assert_eq!(
+        all_day.to_utc_in(&Utc).unwrap().to_rfc3339(),
+        "2025-07-28T00:00:00+00:00"
+    );
+
+    let offset = EventDateTime {
+        time_zone: Some("+02:00".to_string()),
+        ..sample_dt("2025-07-28")
+    };
+    assert_eq!(
+        offset.to_utc().unwrap().to_rfc3339(),
+        "2025-07-27T22:00:00+00:00"
+    );
+}
+
+#[test]
+fn all_day_event_in_an_iana_zone_needs_the_zone() {
+    let event: Event = serde_json::from_str(
+        r#"{"id": "evt",
+            "start": {"date": "2025-07-28", "timeZone": "Europe/Amsterdam"},
+            "end": {"date": "2025-07-29", "timeZone": "Europe/Amsterdam"}}"#,
+    )
+    .unwrap();
+    // Not silently midnight UTC, which is two hours off in Amsterdam in summer
+    assert_eq!(event.start_datetime(), None);
+    assert_eq!(event.end_datetime(), None);
+
+    // Amsterdam is UTC+2 in July, e.g. `chrono_tz::Europe::Amsterdam` resolves the same way
+    let amsterdam_summer = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+    assert_eq!(
+        event
+            .start_datetime_in(&amsterdam_summer)
+            .unwrap()
+            .to_rfc3339(),
+        "2025-07-27T22:00:00+00:00"
+    );
+    assert_eq!(
+        event
+            .end_datetime_in(&amsterdam_summer)
+            .unwrap()
+            .to_rfc3339(),
+        "2025-07-28T22:00:00+00:00"
+    );
+}
+
+#[test]
+fn event_deserializes_recurring_instance_identifiers() {
+    let event: Event = serde_json::from_str(
+        r#"{"id":"abc_20250728","iCalUID":"abc@google.com","recurringEventId":"abc"}"#,
+    )
+    .unwrap();
+    assert_eq!(event.ical_uid, "abc@google.com");
+    assert_eq!(event.recurring_event_id, "abc");
+
+    let event: Event =
+        serde_json::from_str(r#"{"id":"abc","iCalUID":null,"recurringEventId":null}"#).unwrap();
+    assert!(event.ical_uid.is_empty());
+    assert!(event.recurring_event_id.is_empty());
+}
+
+#[test]
+fn default_calendar_requests_use_the_configured_calendar() {
+    let mut gc = dummy_google_client_valid();
+    let client = CalendarEventsClient::new(&gc).get_events_default();
+    assert_eq!(
+        client.request.url,
+        "https://www.googleapis.com/calendar/v3/calendars/primary/events"
+    );
+
+    gc.set_default_calendar("team@group.calendar.google.com");
+    let client = CalendarEventsClient::new(&gc).delete_event_default("evt");
+    assert_eq!(
+        client.request.url,
+        "https://www.googleapis.com/calendar/v3/calendars/team%40group.calendar.google.com/events/evt"
+    );
+}
+
+#[test]
+fn event_list_deserializes_calendar_metadata() {
+    let list: EventList = serde_json::from_str(
+        r#"{
+            "summary": "Work",
+            "timeZone": "Europe/Amsterdam",
+            "accessRole": "owner",
+            "defaultReminders": [{"method": "popup", "minutes": 10}],
+            "nextSyncToken": "sync",
+            "items": []
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(list.summary, "Work");
+    assert_eq!(list.time_zone, "Europe/Amsterdam");
+    assert_eq!(list.access_role, "owner");
+    assert_eq!(list.default_reminders[0].method, "popup");
+    assert_eq!(list.default_reminders[0].minutes, 10);
+    assert_eq!(list.next_sync_token, "sync");
+}
+
+#[test]
+fn raw_param_is_added_to_the_query() {
+    let gc = dummy_google_client_valid();
+    let client = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .raw_param("alwaysIncludeEmail", "true");
+    assert_eq!(
+        client.request.params.get("alwaysIncludeEmail"),
+        Some(&"true".to_string())
+    );
+}
+
+#[test]
+fn event_round_trips_out_of_office_and_birthday_properties() {
+    let json = r#"{
+        "id": "ooo",
+        "outOfOfficeProperties": {
+            "autoDeclineMode": "declineAllConflictingInvitations",
+            "declineMessage": "On holiday"
+        },
+        "birthdayProperties": {"contact": "people/c123", "type": "birthday"}
+    }"#;
+    let event: Event = serde_json::from_str(json).unwrap();
+
+    let ooo = event.out_of_office_properties.as_ref().unwrap();
+    assert_eq!(ooo.auto_decline_mode, "declineAllConflictingInvitations");
+    assert_eq!(ooo.decline_message, "On holiday");
+    let birthday = event.birthday_properties.as_ref().unwrap();
+    assert_eq!(birthday.contact, "people/c123");
+    assert_eq!(birthday.r#type, "birthday");
+
+    let reserialized: Event =
+        serde_json::from_value(serde_json::to_value(&event).unwrap()).unwrap();
+    assert_eq!(reserialized, event);
+}
+
+#[tokio::test]
+async fn insert_event_rejects_end_before_start() {
+    let gc = dummy_google_client_valid();
+    let now = Utc::now();
+    let res = CalendarEventsClient::new(&gc)
+        .insert_event(
+            "primary",
+            When::At(now, None),
+            When::At(now - Duration::hours(1), None),
+        )
+        .request()
+        .await;
+    assert!(res.unwrap_err().to_string().contains("before its start"));
+
+    let res = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-02"), sample_dt("2026-02-02"))
+        .request()
+        .await;
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("must be after its start"));
+}
+
+#[tokio::test]
+async fn patch_event_rejects_end_before_start() {
+    let gc = dummy_google_client_valid();
+    let res = CalendarEventsClient::new(&gc)
+        .patch_event("primary", "evt")
+        .set_start(sample_dt("2026-02-02"))
+        .set_end(sample_dt("2026-02-01"))
+        .request()
+        .await;
+    assert!(res.is_err());
+}
+
+#[test]
+fn event_type_filter_can_be_repeated() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .event_type(EventType::Default)
+        .event_types(vec![EventType::FocusTime, EventType::Birthday])
+        .max_results(10)
+        .max_results(20);
+
+    let p = &builder.request.params;
+    assert_eq!(
+        p.get_all("eventTypes").collect::<Vec<_>>(),
+        ["default", "focusTime", "birthday"]
+    );
+    assert_eq!(p.get_all("maxResults").collect::<Vec<_>>(), ["20"]);
+    let req = reqwest::Client::new()
+        .get(&builder.request.url)
+        .query(p)
+        .build()
+        .unwrap();
+    assert_eq!(
+        req.url().query(),
+        Some("eventTypes=default&eventTypes=focusTime&eventTypes=birthday&maxResults=20")
+    );
+}
+
+#[test]
+fn watch_and_stop_channel_build_channel_bodies() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .watch("primary")
+        .channel_id("chan-1")
+        .address("https://example.com/hook")
+        .token("secret")
+        .ttl(3600);
+
+    assert_eq!(builder.request.method, Method::POST);
+    assert_eq!(
+        builder.request.url,
+        "https://www.googleapis.com/calendar/v3/calendars/primary/events/watch"
+    );
+    let parsed = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        parsed,
+        serde_json::json!({
+            "id": "chan-1",
+            "type": "web_hook",
+            "address": "https://example.com/hook",
+            "token": "secret",
+            "params": { "ttl": "3600" }
+        })
+    );
+
+    let channel: Channel = serde_json::from_str(
+        r#"{"kind":"api#channel","id":"chan-1","resourceId":"res-1","resourceUri":"https://www.googleapis.com/calendar/v3/calendars/primary/events","expiration":"1767225600000"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        channel.expires_at(),
+        chrono::DateTime::from_timestamp(1_767_225_600, 0)
+    );
+
+    let builder = CalendarEventsClient::new(&gc).stop_channel(&channel);
+    assert_eq!(
+        builder.request.url,
+        "https://www.googleapis.com/calendar/v3/channels/stop"
+    );
+    let parsed = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        parsed,
+        serde_json::json!({ "id": "chan-1", "resourceId": "res-1" })
+    );
+}
+
+#[test]
+fn calendar_and_event_ids_are_percent_encoded_in_urls() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc).get_events("team+ops@group.calendar.google.com");
+    assert_eq!(
+        builder.request.url,
+        "https://www.googleapis.com/calendar/v3/calendars/team%2Bops%40group.calendar.google.com/events"
+    );
+
+    let builder = CalendarEventsClient::new(&gc).delete_event("a b@example.com", "evt/1");
+    assert_eq!(
+        builder.request.url,
+        "https://www.googleapis.com/calendar/v3/calendars/a%20b%40example.com/events/evt%2F1"
+    );
+}
+
+#[tokio::test]
+async fn client_from_refresh_token_starts_without_access_token() {
+    let gc = GoogleClient::from_refresh_token(dummy_creds(), true);
+    assert!(gc.access_token().is_none());
+    assert!(!gc.is_access_token_valid());
+    assert!(gc.auto_refresh_token);
+    assert_eq!(
+        gc.client_credentials.refresh_token,
+        dummy_creds().refresh_token
+    );
+
+    let mut creds = dummy_creds();
+    creds.refresh_token.clear();
+    let gc = GoogleClient::from_refresh_token(creds, true);
+    let res = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .request()
+        .await;
+    assert!(
+        matches!(res.unwrap_err(), Error::Auth(message) if message == "client not authenticated")
+    );
+}
+
+#[test]
+fn upcoming_sets_the_next_events_filters() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc).upcoming("primary", 5);
+
+    let p = &builder.request.params;
+    assert_eq!(p.get("singleEvents"), Some(&"true".to_string()));
+    assert_eq!(p.get("orderBy"), Some(&"startTime".to_string()));
+    assert_eq!(p.get("maxResults"), Some(&"5".to_string()));
+    assert!(p.get("timeMin").is_some());
+}
+
+#[test]
+fn changed_since_sets_the_incremental_sync_filters() {
+    let gc = dummy_google_client_valid();
+    let since = Utc::now() - Duration::hours(1);
+    let builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .changed_since(since);
+
+    let p = &builder.request.params;
+    assert_eq!(p.get("updatedMin"), Some(&since.to_rfc3339()));
+    assert_eq!(p.get("showDeleted"), Some(&"true".to_string()));
+    assert_eq!(p.get("singleEvents"), Some(&"true".to_string()));
+
+    let event: Event = serde_json::from_str(r#"{"id": "evt", "status": "cancelled"}"#).unwrap();
+    assert!(event.is_cancelled());
+}
+
+#[test]
+fn attendees_can_be_set_by_email() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_attendees_by_email(&["a@example.com", "b@example.com"]);
+    let parsed = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        parsed["attendees"],
+        serde_json::json!([{ "email": "a@example.com" }, { "email": "b@example.com" }])
+    );
+
+    let builder = CalendarEventsClient::new(&gc)
+        .patch_event("primary", "evt")
+        .set_attendees_by_email(&["c@example.com"]);
+    let parsed = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        parsed["attendees"],
+        serde_json::json!([{ "email": "c@example.com" }])
+    );
+}
+
+#[test]
+fn i64_fields_accept_numbers_and_numeric_strings() {
+    use super::types::EventDefaultReminder;
+
+    for json in [
+        r#"{"method":"popup","minutes":10}"#,
+        r#"{"method":"popup","minutes":"10"}"#,
+    ] {
+        let reminder: EventDefaultReminder = serde_json::from_str(json).unwrap();
+        assert_eq!(reminder.minutes, 10);
+    }
+    let reminder: EventDefaultReminder =
+        serde_json::from_str(r#"{"method":"popup","minutes":null}"#).unwrap();
+    assert_eq!(reminder.minutes, 0);
+    assert!(serde_json::from_str::<EventDefaultReminder>(r#"{"minutes":"ten"}"#).is_err());
+}
+
+#[cfg(feature = "capture-extra")]
+#[test]
+fn event_captures_unmodeled_fields() {
+    let event: Event =
+        serde_json::from_str(r#"{"id":"evt","summary":"Standup","newField":{"a":1}}"#).unwrap();
+    assert_eq!(event.summary, "Standup");
+    assert_eq!(event.extra.len(), 1);
+    assert_eq!(event.extra["newField"], serde_json::json!({ "a": 1 }));
+}
+
+#[tokio::test]
+async fn request_body_content_type_is_set_per_request() {
+    let mut server = mockito::Server::new_async().await;
+    let json = server
+        .mock("POST", "/json")
+        .match_header("content-type", "application/json")
+        .with_status(200)
+        .create_async()
+        .await;
+    let raw = server
+        .mock("POST", "/raw")
+        .match_header("content-type", "message/rfc822")
+        .with_status(200)
+        .create_async()
+        .await;
+    let get = server
+        .mock("GET", "/get")
+        .match_header("content-type", mockito::Matcher::Missing)
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let no_query: &[(&str, &str)] = &[];
+    let res = gc
+        .request_raw(
+            Method::POST,
+            &format!("{}/json", server.url()),
+            no_query,
+            Some("{}".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    let res = gc
+        .request_raw_with_content_type(
+            Method::POST,
+            &format!("{}/raw", server.url()),
+            no_query,
+            Some("Subject: hi\r\n\r\nbody".to_string()),
+            "message/rfc822",
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    let res = gc
+        .request_raw(
+            Method::GET,
+            &format!("{}/get", server.url()),
+            no_query,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+
+    json.assert_async().await;
+    raw.assert_async().await;
+    get.assert_async().await;
+}
+
+#[tokio::test]
+async fn rate_limiter_is_awaited_before_every_request() {
+    use crate::utils::rate_limit::RateLimiter;
+    use futures_util::future::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Counting(Arc<AtomicUsize>);
+    impl RateLimiter for Counting {
+        fn acquire(&self) -> BoxFuture<'_, ()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {})
+        }
+    }
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/limited")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+    let acquired = Arc::new(AtomicUsize::new(0));
+    let mut gc = dummy_google_client_valid();
+    gc.set_rate_limiter(Counting(acquired.clone()));
+    let clone = gc.clone();
+
+    let no_query: &[(&str, &str)] = &[];
+    let url = format!("{}/limited", server.url());
+    gc.request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap();
+    clone
+        .request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap();
+
+    assert_eq!(acquired.load(Ordering::SeqCst), 2);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn conditional_request_sends_if_none_match_and_reports_not_modified() {
+    use crate::utils::request::HeaderRequestTrait;
+
+    let mut server = mockito::Server::new_async().await;
+    let conditional = server
+        .mock("GET", "/events")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(304)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/events")
+        .with_status(200)
+        .with_header("etag", "\"v1\"")
+        .with_body(r#"{"items":[]}"#)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let list = |gc| {
+        let mut builder = CalendarEventsClient::new(gc).get_events("primary");
+        builder.request.url = format!("{}/events", server.url());
+        builder
+    };
+
+    // Without `conditional()` the same request is repeated without `If-None-Match`
+    assert!(list(&gc).request().await.unwrap().is_some());
+    assert!(list(&gc).request().await.unwrap().is_some());
+
+    let listed = list(&gc).conditional().request().await.unwrap();
+    assert!(listed.is_some());
+    let clone = gc.clone();
+    let err = list(&clone).conditional().request().await.unwrap_err();
+    assert!(matches!(err, Error::NotModified));
+
+    first.assert_async().await;
+    conditional.assert_async().await;
+}
+
+#[tokio::test]
+async fn conditional_request_keeps_no_etag_when_the_body_fails_to_decode() {
+    use crate::utils::request::HeaderRequestTrait;
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/events")
+        .match_header("if-none-match", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_header("etag", "\"v1\"")
+        .with_body("not json")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    for _ in 0..2 {
+        let mut builder = CalendarEventsClient::new(&gc)
+            .get_events("primary")
+            .conditional();
+        builder.request.url = format!("{}/events", server.url());
+        assert!(builder.request().await.is_err());
+    }
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn base_url_sends_requests_to_a_mock_server() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/calendar/v3/calendars/primary/events")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "singleEvents".into(),
+            "true".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items":[]}"#)
+        .create_async()
+        .await;
+
+    let mut gc = dummy_google_client_valid();
+    gc.set_base_url(&format!("{}/", server.url()));
+    let mut builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .single_events(true);
+    assert_eq!(
+        builder.dry_run().unwrap().url,
+        format!("{}/calendar/v3/calendars/primary/events", server.url())
+    );
+    assert!(builder.request().await.unwrap().is_some());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn events_collect_up_to_limits_the_last_page_and_resumes() {
+    let mut server = mockito::Server::new_async().await;
+    let second = server
+        .mock("GET", "/calendar/v3/calendars/primary/events")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("pageToken".into(), "p2".into()),
+            mockito::Matcher::UrlEncoded("maxResults".into(), "1".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "c"}], "nextPageToken": "p3", "summary": "Work"}"#)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/calendar/v3/calendars/primary/events")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "maxResults".into(),
+            "2".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "a"}, {"id": "b"}], "nextPageToken": "p2"}"#)
+        .create_async()
+        .await;
+
+    let mut gc = dummy_google_client_valid();
+    gc.set_base_url(&server.url());
+    let events = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .max_results(2)
+        .collect_up_to(Some(3))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = events.items.iter().map(|event| event.id.as_str()).collect();
+    assert_eq!(ids, ["a", "b", "c"]);
+    assert_eq!(events.next_page_token, "p3");
+    assert_eq!(events.summary, "Work");
+    first.assert_async().await;
+    second.assert_async().await;
+
+    let resumed = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .page_token("p3")
+        .collect_up_to(Some(0))
+        .await
+        .unwrap();
+    assert!(resumed.items.is_empty());
+    assert_eq!(resumed.next_page_token, "p3");
+}
+
+#[tokio::test]
+async fn rejected_token_without_auto_refresh_is_unauthorized() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/resource")
+        .with_status(401)
+        .with_body(r#"{"error": {"code": 401, "message": "Invalid Credentials"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    assert!(!gc.auto_refresh_token);
+    let no_query: &[(&str, &str)] = &[];
+    let url = format!("{}/resource", server.url());
+    let err = gc
+        .request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.api_error(),
+        Some(GoogleApiError::Unauthorized { .. })
+    ));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn unsuccessful_response_is_an_api_error() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/events")
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Not Found"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder = CalendarEventsClient::new(&gc).get_events("missing");
+    builder.request.url = format!("{}/events", server.url());
+    let err = builder.request().await.unwrap_err();
+
+    let api_error = err.api_error().unwrap();
+    assert_eq!(api_error.status(), reqwest::StatusCode::NOT_FOUND);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn stale_if_match_patch_is_precondition_failed() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("PATCH", "/event")
+        .match_header("if-match", "\"v1\"")
+        .with_status(412)
+        .with_body(r#"{"error": {"code": 412, "message": "Precondition Failed"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder = CalendarEventsClient::new(&gc)
+        .patch_event("primary", "evt_42")
+        .set_summary("New title")
+        .set_if_match("\"v1\"");
+    builder.request.url = format!("{}/event", server.url());
+    let err = builder.request().await.unwrap_err();
+
+    match err.api_error() {
+        Some(GoogleApiError::PreconditionFailed { message, .. }) => {
+            assert_eq!(message, "Precondition Failed")
+        }
+        other => panic!("expected PreconditionFailed, got {other:?}"),
+    }
+    mock.assert_async().await;
+}
+
+#[test]
+fn patch_from_event_copies_the_writable_fields() {
+    let gc = dummy_google_client_valid();
+    let mut event: Event = serde_json::from_value(serde_json::json!({
+        "id": "evt_42",
+        "etag": "\"v1\"",
+        "summary": "Standup",
+        "location": "",
+        "sequence": 3,
+        "start": { "dateTime": "2026-02-01T09:00:00Z" },
+        "end": { "dateTime": "2026-02-01T09:15:00Z" },
+        "recurrence": ["RRULE:FREQ=DAILY"]
+    }))
+    .unwrap();
+    event.summary = "Moved standup".to_string();
+
+    let builder = CalendarEventsClient::new(&gc)
+        .patch_event("primary", "evt_42")
+        .from_event(event);
+
+    let serialized = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(serialized["summary"], "Moved standup");
+    assert_eq!(serialized["recurrence"][0], "RRULE:FREQ=DAILY");
+    assert!(serialized["start"]["dateTime"].is_string());
+    assert!(serialized.get("location").is_none());
+    assert!(serialized.get("sequence").is_none());
+    assert!(serialized.get("etag").is_none());
+}
+
+#[test]
+fn debug_request_renders_method_url_query_and_body() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .patch_event("primary", "evt_42")
+        .set_summary("New title")
+        .set_if_match("\"v1\"");
+
+    assert_eq!(
+        builder.debug_request(),
+        "PATCH https://www.googleapis.com/calendar/v3/calendars/primary/events/evt_42\n\
+         if-match: \"v1\"\n\
+         Content-Type: application/json\n\n\
+         {\"summary\":\"New title\"}"
+    );
+    let debug = format!("{builder:?}");
+    assert!(debug.contains("New title"));
+    assert!(!debug.contains("atok"));
+
+    let builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .max_results(5);
+    assert_eq!(
+        builder.debug_request(),
+        "GET https://www.googleapis.com/calendar/v3/calendars/primary/events?maxResults=5"
+    );
+}
+
+#[test]
+fn extended_property_filters_can_be_repeated() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .private_extended_property("foreign-id", "X")
+        .private_extended_property("app", "sync")
+        .shared_extended_property("team", "core");
+
+    let p = &builder.request.params;
+    assert_eq!(
+        p.get_all("privateExtendedProperty").collect::<Vec<_>>(),
+        ["foreign-id=X", "app=sync"]
+    );
+    assert_eq!(
+        p.get_all("sharedExtendedProperty").collect::<Vec<_>>(),
+        ["team=core"]
+    );
+}
+
+#[test]
+fn insert_working_location_serializes_and_reads_back() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_event_type(EventType::WorkingLocation)
+        .set_working_location_properties(WorkingLocationProperties::custom_location("Client site"));
+
+    let serialized = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(serialized["eventType"], "workingLocation");
+    assert_eq!(
+        serialized["workingLocationProperties"],
+        serde_json::json!({ "type": "customLocation", "customLocation": { "label": "Client site" } })
+    );
+
+    let event: Event = serde_json::from_value(serde_json::json!({
+        "eventType": "workingLocation",
+        "workingLocationProperties": { "type": "homeOffice", "homeOffice": {} }
+    }))
+    .unwrap();
+    assert_eq!(
+        event.working_location_properties,
+        Some(WorkingLocationProperties::home_office())
+    );
+}
+
+#[tokio::test]
+async fn clones_share_a_token_set_on_one_of_them() {
+    let mut server = mockito::Server::new_async().await;
+    let old = server
+        .mock("GET", "/token")
+        .match_header("authorization", "Bearer atok")
+        .with_status(200)
+        .create_async()
+        .await;
+    let new = server
+        .mock("GET", "/token")
+        .match_header("authorization", "Bearer newtok")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let clone = gc.clone();
+    let no_query: &[(&str, &str)] = &[];
+    let url = format!("{}/token", server.url());
+    clone
+        .request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap();
+    old.assert_async().await;
+
+    gc.set_access_token(AccessToken {
+        token_type: "Bearer".into(),
+        access_token: "newtok".into(),
+        expires_in: 2 * 60 * 60,
+        refresh_token: "rtok".into(),
+        refresh_token_expires_in: 3600,
+        scope: "scope".into(),
+    });
+    clone
+        .request_raw(Method::GET, &url, no_query, None)
+        .await
+        .unwrap();
+    new.assert_async().await;
+    assert_eq!(clone.access_token().unwrap().access_token, "newtok");
+}
+
+#[tokio::test]
+async fn shared_client_sends_concurrent_requests() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/concurrent")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let gc = std::sync::Arc::new(dummy_google_client_valid());
+    let no_query: &[(&str, &str)] = &[];
+    let url = format!("{}/concurrent", server.url());
+    let (first, second) = tokio::join!(
+        gc.request_raw(Method::GET, &url, no_query, None),
+        gc.request_raw(Method::GET, &url, no_query, None),
+    );
+
+    assert_eq!(first.unwrap().status(), 200);
+    assert_eq!(second.unwrap().status(), 200);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn per_request_headers_are_sent_and_override_client_headers() {
+    use crate::utils::request::HeaderRequestTrait;
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/headers")
+        .match_header("x-goog-authuser", "1")
+        .match_header("x-goog-request-reason", "per-request")
+        .with_status(200)
+        .with_body(r#"{"items": []}"#)
+        .create_async()
+        .await;
+
+    let mut gc = dummy_google_client_valid();
+    gc.set_request_reason("client-wide");
+    let mut builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .header("X-Goog-AuthUser", "1")
+        .header("X-Goog-Request-Reason", "per-request");
+    builder.request.url = format!("{}/headers", server.url());
+    assert!(builder.request().await.unwrap().is_some());
+    mock.assert_async().await;
+
+    let mut invalid = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .header("X-Bad", "line\nbreak");
+    assert!(matches!(
+        invalid.request().await,
+        Err(Error::InvalidRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn auth_user_and_quota_user_are_sent() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/quota")
+        .match_header("x-goog-authuser", "2")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "quotaUser".into(),
+            "tenant-42".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": []}"#)
+        .create_async()
+        .await;
+
+    let mut gc = dummy_google_client_valid();
+    gc.set_auth_user(2);
+    let mut builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .quota_user("tenant-42");
+    builder.request.url = format!("{}/quota", server.url());
+    assert!(builder.request().await.unwrap().is_some());
+    mock.assert_async().await;
+
+    let prepared = builder.dry_run().unwrap();
+    assert_eq!(prepared.headers["x-goog-authuser"], "2");
+    assert_eq!(
+        prepared.query.get("quotaUser"),
+        Some(&"tenant-42".to_string())
+    );
+}
+
+#[tokio::test]
+async fn sent_body_is_kept_on_the_request() {
+    let mut server = mockito::Server::new_async().await;
+    let gc = dummy_google_client_valid();
+    let mut builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_summary("Standup");
+    builder.request.url = format!("{}/events", server.url());
+    let expected = builder.dry_run().unwrap().body.unwrap();
+
+    let mock = server
+        .mock("POST", "/events")
+        .match_body(mockito::Matcher::Exact(expected.clone()))
+        .with_status(200)
+        .with_body(r#"{"id": "evt"}"#)
+        .create_async()
+        .await;
+    assert!(builder.request().await.unwrap().is_some());
+    mock.assert_async().await;
+    assert_eq!(builder.request.body, Some(expected));
+}
+
+#[test]
+fn event_status_is_typed() {
+    use super::types::EventStatus;
+
+    let event: Event = serde_json::from_str(r#"{"id": "evt", "status": "tentative"}"#).unwrap();
+    assert_eq!(
+        event.event_status(),
+        Some(FetchedEventStatus::Known(EventStatus::Tentative))
+    );
+    assert!(!event.is_cancelled());
+    let event: Event = serde_json::from_str(r#"{"id": "evt"}"#).unwrap();
+    assert_eq!(event.event_status(), None);
+
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_event_status(EventStatus::Tentative);
+    let parsed = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(parsed["status"], "tentative");
+
+    let builder = CalendarEventsClient::new(&gc)
+        .patch_event("primary", "evt")
+        .set_event_status(EventStatus::Cancelled);
+    let parsed = serde_json::to_value(builder.event.as_ref().unwrap()).unwrap();
+    assert_eq!(parsed["status"], "cancelled");
+    assert_eq!(
+        serde_json::to_value(EventStatus::Confirmed).unwrap(),
+        "confirmed"
+    );
+}
+
+#[test]
+fn unknown_status_is_only_readable() {
+    // Only the statuses that can be sent are accepted as `EventStatus`
+    assert!(serde_json::from_str::<EventStatus>(r#""postponed""#).is_err());
+
+    let event: Event = serde_json::from_str(r#"{"id": "evt", "status": "postponed"}"#).unwrap();
+    assert_eq!(
+        event.event_status(),
+        Some(FetchedEventStatus::Unknown("postponed".to_string()))
+    );
+    assert!(!event.is_cancelled());
+}
+
+#[test]
+fn send_updates_is_shared_by_insert_and_delete() {
+    let gc = dummy_google_client_valid();
+    let builder = CalendarEventsClient::new(&gc)
+        .insert_event("primary", sample_dt("2026-02-01"), sample_dt("2026-02-02"))
+        .set_send_updates(SendUpdates::ExternalOnly);
+    assert_eq!(
+        builder.request.params.get("sendUpdates"),
+        Some(&"externalOnly".to_string())
+    );
+
+    let builder = CalendarEventsClient::new(&gc)
+        .delete_event("primary", "evt")
+        .send_updates(SendUpdates::None);
+    assert_eq!(
+        builder.request.params.get("sendUpdates"),
+        Some(&"none".to_string())
+    );
+}
+
+#[test]
+fn event_list_splits_all_day_and_timed_events() {
+    let list: EventList = serde_json::from_str(
+        r#"{"items": [
+            {"id": "holiday", "start": {"date": "2026-02-01"}},
+            {"id": "standup", "start": {"dateTime": "2026-02-02T09:00:00Z"}},
+            {"id": "cancelled", "status": "cancelled"}
+        ]}"#,
+    )
+    .unwrap();
+
+    let all_day: Vec<_> = list
+        .all_day_events()
+        .iter()
+        .map(|e| e.id.as_str())
+        .collect();
+    assert_eq!(all_day, ["holiday"]);
+    let timed: Vec<_> = list.timed_events().iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(timed, ["standup"]);
+}
+
+#[tokio::test]
+async fn all_walks_every_page() {
+    use mockito::Matcher;
+
+    let mut server = mockito::Server::new_async().await;
+    // Mocks are matched in creation order, the second page is more specific
+    let second = server
+        .mock("GET", "/events")
+        .match_query(Matcher::UrlEncoded("pageToken".into(), "p2".into()))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "c"}]}"#)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/events")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("maxResults".into(), "2500".into()),
+            Matcher::UrlEncoded("singleEvents".into(), "true".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "a"}, {"id": "b"}], "nextPageToken": "p2"}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder = CalendarEventsClient::new(&gc)
+        .get_events("primary")
+        .single_events(true);
+    builder.request.url = format!("{}/events", server.url());
+    let events = builder.all().await.unwrap();
+
+    first.assert_async().await;
+    second.assert_async().await;
+    let ids: Vec<_> = events.iter().map(|event| event.id.as_str()).collect();
+    assert_eq!(ids, ["a", "b", "c"]);
+}
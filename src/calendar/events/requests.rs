@@ -1,19 +1,45 @@
 use crate::{
     auth::client::GoogleClient,
     calendar::events::types::{CreateEventRequest, EventDateTime},
-    utils::request::{PaginationRequestTrait, Request, TimeRequestTrait},
+    error::GoogleApiError,
+    utils::request::{
+        HeaderRequestTrait, PaginationRequestTrait, PreparedRequest, RawParamRequestTrait, Request,
+        TimeRequestTrait,
+    },
+    utils::url::encode_segment,
 };
 
-use anyhow::{anyhow, Error};
+use crate::error::Error;
 use chrono::DateTime;
 use reqwest::Method;
 use serde::{de::DeserializeOwned, Serialize};
 
 use super::types::{
-    BirthdayProperties, Event, EventAttendee, EventList, EventReminders, EventSource,
-    OutOfOfficeProperties, PatchEventRequest, WorkingLocationProperties,
+    BirthdayProperties, Channel, Event, EventAttendee, EventList, EventReminders, EventSource,
+    EventStatus, OutOfOfficeProperties, PatchEventRequest, Visibility, WatchParams, WatchRequest,
+    WorkingLocationProperties,
 };
 
+/// The largest page size Google allows when listing events.
+pub const MAX_EVENT_PAGE_SIZE: i64 = 2500;
+
+/// The most pages `all` retrieves before giving up.
+pub const MAX_EVENT_PAGES: usize = 100;
+
+/// Builds the URL of the events of a calendar, or of a single event when an event id is given.
+/// The ids are percent-encoded since calendar ids are usually email addresses.
+fn events_url(calendar_id: &str, event_id: Option<&str>) -> String {
+    let mut url = format!(
+        "https://www.googleapis.com/calendar/v3/calendars/{}/events",
+        encode_segment(calendar_id)
+    );
+    if let Some(event_id) = event_id {
+        url.push('/');
+        url.push_str(&encode_segment(event_id));
+    }
+    url
+}
+
 /// Indicates that the request builder is not yet initialized with a specific mode.
 pub struct Uninitialized;
 /// Indicates that the request builder is initialized for retrieving single events.
@@ -30,12 +56,19 @@ pub struct EventDeleteMode;
 pub struct EventInsertMode;
 
 pub struct EventPatchMode;
+/// Indicates that the request builder is initialized for watching the events of a calendar.
+/// This struct determines which filters can be applied to the request.
+pub struct EventWatchMode;
+/// Indicates that the request builder is initialized for stopping a notification channel.
+pub struct ChannelStopMode;
 
 #[derive(Serialize)]
 #[serde(untagged)]
 pub enum EventRequest {
     Create(CreateEventRequest),
     Patch(PatchEventRequest),
+    Watch(WatchRequest),
+    Channel(Channel),
 }
 
 /// The generic type parameter `T` determines the mode of operation for this client,
@@ -50,7 +83,7 @@ pub struct CalendarEventsClient<'a, T = Uninitialized> {
 /// This provides the entry points to initialize the client for specific operations.
 impl<'a> CalendarEventsClient<'a, Uninitialized> {
     /// Creates a new calendar events client using the provided Google client for authentication.
-    pub fn new(client: &'a mut GoogleClient) -> Self {
+    pub fn new(client: &'a GoogleClient) -> Self {
         Self {
             request: Request::new(client),
             event: None,
@@ -65,7 +98,7 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
     ///  
     /// ``` rust
     /// #[axum::debug_handler]
-    /// pub async fn get_birtday_events(State(state): State<AppState>) -> Json<EventResponse> {
+    /// pub async fn get_birthday_events(State(state): State<AppState>) -> Json<EventResponse> {
     ///     //GoogleClient is stored in the AppState wrapped in a Arc<Mutex>
     ///     let google_client_guard = state.google_client.lock().await;
     ///     let client = google_client_guard.as_ref().unwrap();
@@ -93,9 +126,7 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
             event: None,
             _mode: std::marker::PhantomData,
         };
-        builder.request.url = "https://www.googleapis.com/calendar/v3/calendars/".to_string()
-            + calendar_id
-            + "/events";
+        builder.request.url = events_url(calendar_id, None);
         builder.request.method = reqwest::Method::GET;
         builder
     }
@@ -105,8 +136,8 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
     /// # Arguments
     ///
     /// * `calendar_id` - The ID of the calendar where the event will be created
-    /// * `start` - The start time information for the event
-    /// * `end` - The end time information for the event
+    /// * `start` - The start time information for the event, either an `EventDateTime` or a `When`
+    /// * `end` - The end time information for the event, either an `EventDateTime` or a `When`
     ///
     /// # Returns
     ///
@@ -119,8 +150,8 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
     ///  
     /// ```rust
     /// pub async fn insert_new_event(State(state): State<AppState>) {
-    ///     let mut google_client_guard = state.google_client.lock().await;
-    ///     let client = google_client_guard.as_mut().unwrap();
+    ///     let google_client_guard = state.google_client.lock().await;
+    ///     let client = google_client_guard.as_ref().unwrap();
     ///     let start: EventDateTime = EventDateTime {
     ///         date: Some("2025-07-28".to_string()),
     ///         date_time: None,
@@ -137,20 +168,30 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
     ///         .set_description("new event")
     ///         .request()
     ///         .await;
+    ///
+    ///     // The When enum makes sure only one of date or date_time is set
+    ///     let day = chrono::NaiveDate::from_ymd_opt(2025, 7, 28).unwrap();
+    ///     CalendarEventsClient::new(client)
+    ///         .insert_event("calendar_id", When::AllDay(day), When::AllDay(day))
+    ///         .set_summary("all day event")
+    ///         .request()
+    ///         .await;
     /// }
     pub fn insert_event(
         self,
         calendar_id: &str,
-        start: EventDateTime,
-        end: EventDateTime,
+        start: impl Into<EventDateTime>,
+        end: impl Into<EventDateTime>,
     ) -> CalendarEventsClient<'a, EventInsertMode> {
         let mut builder = CalendarEventsClient {
             request: self.request,
-            event: Some(EventRequest::Create(CreateEventRequest::new(start, end))),
+            event: Some(EventRequest::Create(CreateEventRequest::new(
+                start.into(),
+                end.into(),
+            ))),
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://www.googleapis.com/calendar/v3/calendars/{calendar_id}/events",);
+        builder.request.url = events_url(calendar_id, None);
         builder.request.method = Method::POST;
         builder
     }
@@ -173,8 +214,8 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
     ///
     /// ``` rust
     ///     async fn update_event(State(state): State<AppState>) {
-    ///     let mut google_client_guard = state.google_client.lock().await;
-    ///     let client = google_client_guard.as_mut().unwrap();
+    ///     let google_client_guard = state.google_client.lock().await;
+    ///     let client = google_client_guard.as_ref().unwrap();
     ///     CalendarEventsClient::new(client)
     ///      // Main callendar can be targetd by the string "primary" as id
     ///      .patch_event("calendar_id", "event_id")
@@ -195,9 +236,7 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
             event: Some(EventRequest::Patch(PatchEventRequest::default())),
             _mode: std::marker::PhantomData,
         };
-        builder.request.url = format!(
-            "https://www.googleapis.com/calendar/v3/calendars/{calendar_id}/events/{event_id}"
-        );
+        builder.request.url = events_url(calendar_id, Some(event_id));
         builder.request.method = Method::PATCH;
         builder
     }
@@ -212,16 +251,109 @@ impl<'a> CalendarEventsClient<'a, Uninitialized> {
             event: None,
             _mode: std::marker::PhantomData,
         };
-        builder.request.url = format!(
-            "https://www.googleapis.com/calendar/v3/calendars/{calendar_id}/events/{event_id}"
-        );
+        builder.request.url = events_url(calendar_id, Some(event_id));
         builder.request.method = Method::DELETE;
         builder
     }
+
+    /// Subscribes to push notifications for changes to the events of a calendar.
+    /// Google sends a notification to the webhook `address` whenever an event changes,
+    /// the returned `Channel` is needed to stop the subscription with `stop_channel`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let channel = CalendarEventsClient::new(&client)
+    ///     .watch("primary")
+    ///     .channel_id("01234567-89ab-cdef-0123456789ab")
+    ///     .address("https://example.com/notifications")
+    ///     .ttl(3600)
+    ///     .request()
+    ///     .await?;
+    /// ```
+    pub fn watch(self, calendar_id: &str) -> CalendarEventsClient<'a, EventWatchMode> {
+        let mut builder = CalendarEventsClient {
+            request: self.request,
+            event: Some(EventRequest::Watch(WatchRequest {
+                type_: "web_hook".to_string(),
+                ..Default::default()
+            })),
+            _mode: std::marker::PhantomData,
+        };
+        builder.request.url = events_url(calendar_id, None) + "/watch";
+        builder.request.method = Method::POST;
+        builder
+    }
+
+    /// Stops receiving notifications for a channel created with `watch`.
+    pub fn stop_channel(self, channel: &Channel) -> CalendarEventsClient<'a, ChannelStopMode> {
+        let mut builder = CalendarEventsClient {
+            request: self.request,
+            event: Some(EventRequest::Channel(Channel {
+                id: channel.id.clone(),
+                resource_id: channel.resource_id.clone(),
+                ..Default::default()
+            })),
+            _mode: std::marker::PhantomData,
+        };
+        builder.request.url = "https://www.googleapis.com/calendar/v3/channels/stop".to_string();
+        builder.request.method = Method::POST;
+        builder
+    }
+
+    /// Lists the next `n` upcoming events of a calendar, recurring events are expanded into
+    /// their instances and ordered by start time.
+    /// Shorthand for `get_events` with `single_events(true)`, `order_by(EventOrderBy::StartTime)`,
+    /// `time_min(now)` and `max_results(n)`, the filters can still be adjusted afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let events = CalendarEventsClient::new(&client)
+    ///     .upcoming("primary", 10)
+    ///     .request()
+    ///     .await?;
+    /// ```
+    pub fn upcoming(self, calendar_id: &str, n: i64) -> CalendarEventsClient<'a, EventListMode> {
+        self.get_events(calendar_id)
+            .single_events(true)
+            .order_by(EventOrderBy::StartTime)
+            .time_min(chrono::Utc::now())
+            .max_results(n)
+    }
+
+    /// Same as `get_events` on the client's default calendar, see `GoogleClient::set_default_calendar`.
+    pub fn get_events_default(self) -> CalendarEventsClient<'a, EventListMode> {
+        let calendar_id = self.request.client.default_calendar().to_string();
+        self.get_events(&calendar_id)
+    }
+
+    /// Same as `insert_event` on the client's default calendar, see `GoogleClient::set_default_calendar`.
+    pub fn insert_event_default(
+        self,
+        start: impl Into<EventDateTime>,
+        end: impl Into<EventDateTime>,
+    ) -> CalendarEventsClient<'a, EventInsertMode> {
+        let calendar_id = self.request.client.default_calendar().to_string();
+        self.insert_event(&calendar_id, start, end)
+    }
+
+    /// Same as `patch_event` on the client's default calendar, see `GoogleClient::set_default_calendar`.
+    pub fn patch_event_default(self, event_id: &str) -> CalendarEventsClient<'a, EventPatchMode> {
+        let calendar_id = self.request.client.default_calendar().to_string();
+        self.patch_event(&calendar_id, event_id)
+    }
+
+    /// Same as `delete_event` on the client's default calendar, see `GoogleClient::set_default_calendar`.
+    pub fn delete_event_default(self, event_id: &str) -> CalendarEventsClient<'a, EventDeleteMode> {
+        let calendar_id = self.request.client.default_calendar().to_string();
+        self.delete_event(&calendar_id, event_id)
+    }
 }
 
 /// Event ordering options for Google Calendar events.
 /// StartTime doesn't work with recurring events unless singleEvents is set to true.
+#[non_exhaustive]
 pub enum EventOrderBy {
     StartTime,
     Updated,
@@ -240,6 +372,7 @@ impl EventOrderBy {
 * See [Google Calendar API
 * documentation](https://developers.google.com/calendar/api/v3/reference/events
 */
+#[non_exhaustive]
 pub enum EventType {
     Birthday,
     Default,
@@ -261,6 +394,59 @@ impl EventType {
     }
 }
 
+/// Who Google notifies about a created, changed or deleted event.
+#[non_exhaustive]
+pub enum SendUpdates {
+    /// Notifications are sent to all guests.
+    All,
+    /// Notifications are sent to non-Google Calendar guests only.
+    ExternalOnly,
+    /// No notifications are sent.
+    None,
+}
+impl SendUpdates {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SendUpdates::All => "all",
+            SendUpdates::ExternalOnly => "externalOnly",
+            SendUpdates::None => "none",
+        }
+    }
+}
+
+/// The event colors available in Google Calendar, mapping to color ids "1" to "11".
+pub enum EventColor {
+    Lavender,
+    Sage,
+    Grape,
+    Flamingo,
+    Banana,
+    Tangerine,
+    Peacock,
+    Graphite,
+    Blueberry,
+    Basil,
+    Tomato,
+}
+
+impl EventColor {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventColor::Lavender => "1",
+            EventColor::Sage => "2",
+            EventColor::Grape => "3",
+            EventColor::Flamingo => "4",
+            EventColor::Banana => "5",
+            EventColor::Tangerine => "6",
+            EventColor::Peacock => "7",
+            EventColor::Graphite => "8",
+            EventColor::Blueberry => "9",
+            EventColor::Basil => "10",
+            EventColor::Tomato => "11",
+        }
+    }
+}
+
 impl<'a> PaginationRequestTrait for CalendarEventsClient<'a, EventListMode> {
     /// Maximum number of results to return.
     fn max_results(mut self, max: i64) -> Self {
@@ -279,6 +465,29 @@ impl<'a> PaginationRequestTrait for CalendarEventsClient<'a, EventListMode> {
     }
 }
 
+impl<'a, T> RawParamRequestTrait for CalendarEventsClient<'a, T> {
+    /// Sets an arbitrary query parameter, overriding any value set by the builder methods.
+    fn raw_param(mut self, key: &str, value: &str) -> Self {
+        self.request
+            .params
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+impl<'a, T> HeaderRequestTrait for CalendarEventsClient<'a, T> {
+    /// Sets a header for this request only, overriding a client-wide value of the header.
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.request.set_header(name, value);
+        self
+    }
+
+    fn conditional(mut self) -> Self {
+        self.request.conditional = true;
+        self
+    }
+}
+
 impl<'a> TimeRequestTrait for CalendarEventsClient<'a, EventListMode> {
     /// Minimum time for events to return. If not set, all historicall events matching the other
     /// filters are returned.
@@ -300,10 +509,38 @@ impl<'a> TimeRequestTrait for CalendarEventsClient<'a, EventListMode> {
 
 impl<'a> CalendarEventsClient<'a, EventListMode> {
     /// Set the type of events to filter by.
+    /// Call this multiple times to return events of any of the given types.
     pub fn event_type(mut self, type_: EventType) -> Self {
         self.request
             .params
-            .insert("eventTypes".to_string(), type_.as_str().to_string());
+            .append("eventTypes".to_string(), type_.as_str().to_string());
+        self
+    }
+
+    /// Set multiple types of events to filter by, events of any of the given types are returned.
+    pub fn event_types(self, types: Vec<EventType>) -> Self {
+        types
+            .into_iter()
+            .fold(self, |builder, type_| builder.event_type(type_))
+    }
+
+    /// Only return events with the private extended property `key` set to `value`.
+    /// Call this multiple times to only return events that match all constraints.
+    pub fn private_extended_property(mut self, key: &str, value: &str) -> Self {
+        self.request.params.append(
+            "privateExtendedProperty".to_string(),
+            format!("{key}={value}"),
+        );
+        self
+    }
+
+    /// Only return events with the shared extended property `key` set to `value`.
+    /// Call this multiple times to only return events that match all constraints.
+    pub fn shared_extended_property(mut self, key: &str, value: &str) -> Self {
+        self.request.params.append(
+            "sharedExtendedProperty".to_string(),
+            format!("{key}={value}"),
+        );
         self
     }
 
@@ -334,6 +571,32 @@ impl<'a> CalendarEventsClient<'a, EventListMode> {
         self
     }
 
+    /// If set to true cancelled events are returned too, with `status` "cancelled".
+    pub fn show_deleted(mut self, show: bool) -> Self {
+        self.request
+            .params
+            .insert("showDeleted".to_string(), show.to_string());
+        self
+    }
+
+    /// Only return events that were modified after this time, including deleted events when
+    /// `show_deleted` is set.
+    pub fn updated_min(mut self, updated_min: DateTime<chrono::Utc>) -> Self {
+        self.request
+            .params
+            .insert("updatedMin".to_string(), updated_min.to_rfc3339());
+        self
+    }
+
+    /// Returns the events that changed after `since` for incremental sync, including the
+    /// cancelled ones so deletions are picked up, see `Event::is_cancelled`.
+    /// Shorthand for `updated_min(since)`, `show_deleted(true)` and `single_events(true)`.
+    pub fn changed_since(self, since: DateTime<chrono::Utc>) -> Self {
+        self.updated_min(since)
+            .show_deleted(true)
+            .single_events(true)
+    }
+
     /// Filter if set to true shows hidden invitations.
     pub fn show_hidden_invitations(mut self, max: bool) -> Self {
         self.request
@@ -342,6 +605,15 @@ impl<'a> CalendarEventsClient<'a, EventListMode> {
         self
     }
 
+    /// If set to true the email of the organizer and attendees is always included, even when
+    /// the email isn't available. A generated, non working value is returned in that case.
+    pub fn always_include_email(mut self, include: bool) -> Self {
+        self.request
+            .params
+            .insert("alwaysIncludeEmail".to_string(), include.to_string());
+        self
+    }
+
     /// Add a query string to the request.
     /// This searches for events matching the query string in the fields:
     /// location, summary, description, and attendees.
@@ -356,86 +628,152 @@ impl<'a> CalendarEventsClient<'a, EventListMode> {
     pub async fn request(&mut self) -> Result<Option<EventList>, Error> {
         self.make_request().await
     }
+
+    /// Retrieves the events of all pages matching the filters, following the `next_page_token`
+    /// of every page. Pages of `MAX_EVENT_PAGE_SIZE` events are requested unless `max_results`
+    /// is set. Fails when any page couldn't be retrieved, or when there are more than
+    /// `MAX_EVENT_PAGES` pages so a runaway listing doesn't exhaust the quota.
+    pub async fn all(mut self) -> Result<Vec<Event>, Error> {
+        if self.request.params.get("maxResults").is_none() {
+            self = self.max_results(MAX_EVENT_PAGE_SIZE);
+        }
+        let mut events = Vec::new();
+        for _ in 0..MAX_EVENT_PAGES {
+            let Some(page) = self.request().await? else {
+                return Err(Error::Request("Failed to list the events".to_string()));
+            };
+            events.extend(page.items);
+            if page.next_page_token.is_empty() {
+                return Ok(events);
+            }
+            self = self.page_token(&page.next_page_token);
+        }
+        Err(Error::Request(format!(
+            "Listing the events exceeded {MAX_EVENT_PAGES} pages"
+        )))
+    }
+
+    /// Retrieves the events of all pages, stopping once `max_items` events are retrieved.
+    /// The page size is reduced for the last page so no event is skipped, the returned
+    /// `next_page_token` resumes right after the last returned event and is empty when all
+    /// events were retrieved. The other fields are those of the last page, like the
+    /// `next_sync_token`.
+    pub async fn collect_up_to(mut self, max_items: Option<usize>) -> Result<EventList, Error> {
+        let mut events = EventList {
+            next_page_token: self.request.page_token(),
+            ..EventList::default()
+        };
+        while self.request.limit_page_size(max_items, events.items.len()) {
+            let Some(mut page) = self.request().await? else {
+                return Err(Error::Request("Failed to list the events".to_string()));
+            };
+            let mut items = std::mem::take(&mut events.items);
+            items.append(&mut page.items);
+            events = EventList { items, ..page };
+            if events.next_page_token.is_empty() {
+                break;
+            }
+            self = self.page_token(&events.next_page_token);
+        }
+        Ok(events)
+    }
+}
+
+/// The client is left out, it holds the tokens.
+impl<T> std::fmt::Debug for CalendarEventsClient<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CalendarEventsClient")
+            .field("request", &self.request)
+            .field("payload", &self.payload())
+            .finish()
+    }
 }
 
 impl<'a, T> CalendarEventsClient<'a, T> {
-    pub(super) async fn make_delete_request(&mut self) -> Result<bool, Error> {
-        self.request.client.refresh_access_token_check().await?;
-        let res = self
-            .request
-            .client
-            .req_client
-            .delete(&self.request.url)
-            .query(&self.request.params)
-            .send()
-            .await?;
+    /// Renders the method, URL, query and body of the request for troubleshooting,
+    /// see `Request::debug_request`.
+    pub fn debug_request(&self) -> String {
+        self.request.debug_request(self.payload().as_deref())
+    }
+
+    /// Returns the request as it would be sent, without sending it.
+    pub fn dry_run(&self) -> Result<PreparedRequest, Error> {
+        self.request.prepare(self.body()?)
+    }
+
+    /// The JSON body the request is sent with, requests without a body ignore the event.
+    fn body(&self) -> Result<Option<String>, Error> {
+        if matches!(self.request.method, Method::GET | Method::DELETE) {
+            return Ok(None);
+        }
+        match &self.event {
+            Some(event) => Ok(Some(serde_json::to_string(event)?)),
+            None => Ok(self.request.body.clone()),
+        }
+    }
+
+    /// Same as `body` for the debug output, which can't fail.
+    fn payload(&self) -> Option<String> {
+        self.body().ok().flatten()
+    }
+    pub(super) async fn make_delete_request(&mut self) -> Result<(), Error> {
+        let res = self.request.send(None).await?;
 
         if res.status().is_success() {
-            Ok(true)
+            Ok(())
         } else {
-            Ok(false)
+            Err(GoogleApiError::from_response(res).await.into())
         }
     }
+    /// Google answers an end before the start with an unhelpful 400, so the range is checked
+    /// before sending. Timed events may end at their start, the end date of all-day events is
+    /// exclusive so it has to be after the start date. Mixed ranges are left to Google.
+    fn validate_event_range(&self) -> Result<(), Error> {
+        let (start, end) = match &self.event {
+            Some(EventRequest::Create(event)) => (Some(&event.start), Some(&event.end)),
+            Some(EventRequest::Patch(event)) => (event.start.as_ref(), event.end.as_ref()),
+            _ => (None, None),
+        };
+        let (Some(start), Some(end)) = (start, end) else {
+            return Ok(());
+        };
+
+        if let (Some(start), Some(end)) = (start.date_time, end.date_time) {
+            if end < start {
+                return Err(Error::InvalidRequest(format!(
+                    "Event end {end} is before its start {start}"
+                )));
+            }
+        } else if let (Some(start), Some(end)) = (&start.date, &end.date) {
+            // Dates are formatted as yyyy-mm-dd so they compare chronologically as strings
+            if end <= start {
+                return Err(Error::InvalidRequest(format!(
+                    "All-day event end date {end} must be after its start date {start}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     pub(super) async fn make_request<R>(&mut self) -> Result<Option<R>, Error>
     where
         R: DeserializeOwned,
     {
-        self.request.client.refresh_access_token_check().await?;
-        match self.request.method {
-            Method::GET => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .get(&self.request.url)
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
-
-            Method::POST => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .post(&self.request.url)
-                    .body(serde_json::to_string(&self.event).unwrap())
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
-
-            Method::PATCH => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .patch(&self.request.url)
-                    .body(serde_json::to_string(&self.event).unwrap())
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
+        if !matches!(
+            self.request.method,
+            Method::GET | Method::POST | Method::PATCH
+        ) {
+            return Err(Error::InvalidRequest("Unsupported HTTP method".to_string()));
+        }
+        // Kept on the request, so the body that was sent can be inspected afterwards
+        self.request.body = self.body()?;
+        let body = self.request.body.clone();
+        let res = self.request.send(body).await?;
 
-            _ => Err(anyhow!("Unsupported HTTP method")),
+        if res.status().is_success() {
+            Ok(Some(self.request.read_json(res).await?))
+        } else {
+            Err(GoogleApiError::from_response(res).await.into())
         }
     }
 }
@@ -455,7 +793,7 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
     ///
     /// # Arguments
     ///
-    /// * `description` - The summary text to set for the event
+    /// * `descr` - The description text to set for the event, this can contain HTML
     pub fn set_description(self, descr: &str) -> Self {
         self.modify_event(|event| event.description = Some(descr.to_string()))
     }
@@ -469,6 +807,24 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
         self.modify_event(|event| event.location = Some(location.to_string()))
     }
 
+    /// Sets the status of the event, e.g. `EventStatus::Tentative`. Defaults to confirmed.
+    pub fn set_event_status(self, status: EventStatus) -> Self {
+        self.modify_event(|event| event.status = Some(status.as_str().to_string()))
+    }
+
+    /// Sets who can see the details of the event, defaults to `Visibility::Default`.
+    pub fn set_visibility(self, visibility: Visibility) -> Self {
+        self.modify_event(|event| event.visibility = Some(visibility.as_str().to_string()))
+    }
+
+    /// Guests who should receive an invitation for the new event, Google defaults to none.
+    pub fn set_send_updates(mut self, send: SendUpdates) -> Self {
+        self.request
+            .params
+            .insert("sendUpdates".to_string(), send.as_str().to_string());
+        self
+    }
+
     /// Sets the attendees for the event.
     ///
     /// # Arguments
@@ -478,11 +834,25 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
         self.modify_event(|event| event.attendees = attendees)
     }
 
+    /// Sets the attendees of the event from their email addresses.
+    ///
+    /// # Arguments
+    ///
+    /// * `emails` - The email addresses of the attendees
+    pub fn set_attendees_by_email(self, emails: &[&str]) -> Self {
+        self.set_attendees(
+            emails
+                .iter()
+                .map(|email| EventAttendee::from_email(email))
+                .collect(),
+        )
+    }
+
     /// Sets the type of event.
     ///
     /// # Arguments
     ///
-    /// * `type_` - The EventType to set for the event
+    /// * `event_type` - The EventType to set for the event
     ///
     /// pub enum EventType {
     ///    Birthday,
@@ -491,17 +861,29 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
     ///    FromGmail,
     ///    OutOfOffice,
     /// }
+    pub fn set_event_type(self, event_type: EventType) -> Self {
+        self.modify_event(|event| event.event_type = Some(event_type.as_str().to_string()))
+    }
+
+    /// Sets the type of event.
+    #[deprecated(note = "use `set_event_type`, which is also available when patching events")]
     pub fn set_type(self, type_: EventType) -> Self {
-        self.modify_event(|event| event.event_type = Some(type_.as_str().to_string()))
+        self.set_event_type(type_)
     }
 
     /// Sets the birthday properties for the event.
     ///
     /// # Arguments
     ///
-    /// * `birtday_properties` - The BirthdayProperties to set for the event
-    pub fn set_birtday_properties(self, birtday_properties: BirthdayProperties) -> Self {
-        self.modify_event(|event| event.birthday_properties = Some(birtday_properties))
+    /// * `birthday_properties` - The BirthdayProperties to set for the event
+    pub fn set_birthday_properties(self, birthday_properties: BirthdayProperties) -> Self {
+        self.modify_event(|event| event.birthday_properties = Some(birthday_properties))
+    }
+
+    /// Sets the birthday properties for the event.
+    #[deprecated(note = "use `set_birthday_properties`")]
+    pub fn set_birtday_properties(self, birthday_properties: BirthdayProperties) -> Self {
+        self.set_birthday_properties(birthday_properties)
     }
 
     /// Sets the color ID for the event.
@@ -513,6 +895,11 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
         self.modify_event(|event| event.color_id = Some(color_id.to_string()))
     }
 
+    /// Sets the color of the event, use `set_color_id` for colors that aren't in `EventColor`.
+    pub fn set_color(self, color: EventColor) -> Self {
+        self.set_color_id(color.as_str())
+    }
+
     /// Sets whether guests can invite others to the event.
     ///
     /// # Arguments
@@ -561,6 +948,16 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
         self.modify_event(|event| event.out_of_office_properties = Some(out_of_office_properties))
     }
 
+    /// Sets the working location properties for the event, Google only accepts them on events
+    /// of type `EventType::WorkingLocation`.
+    ///
+    /// # Arguments
+    ///
+    /// * `properties` - The WorkingLocationProperties to set for the event
+    pub fn set_working_location_properties(self, properties: WorkingLocationProperties) -> Self {
+        self.modify_event(|event| event.working_location_properties = Some(properties))
+    }
+
     /// Sets the recurrence rules for the event.
     ///
     /// # Arguments
@@ -575,9 +972,10 @@ impl<'a> CalendarEventsClient<'a, EventInsertMode> {
     /// # Returns
     ///
     /// * `Ok(Some(Event))` - The created event if successful
-    /// * `Ok(None)` - If the request was unsuccessful
-    /// * `Err` - If there was an error making the request
+    /// * `Err(Error::Api)` - If Google responded with an unsuccessful status code
+    /// * `Err` - If there was another error making the request or the end is before the start
     pub async fn request(&mut self) -> Result<Option<Event>, Error> {
+        self.validate_event_range()?;
         self.make_request().await
     }
 
@@ -597,18 +995,18 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
     ///  
     ///  # Arguments
     ///
-    ///  * `end` - new EventDateTime format
-    pub fn set_end(self, end: EventDateTime) -> Self {
-        self.modify_event(|event| event.end = Some(end))
+    ///  * `end` - new EventDateTime format or a When
+    pub fn set_end(self, end: impl Into<EventDateTime>) -> Self {
+        self.modify_event(|event| event.end = Some(end.into()))
     }
 
     /// Patch the start of the event
     ///  
     ///  # Arguments
     ///
-    ///  * `start` - new EventDateTime format
-    pub fn set_start(self, start: EventDateTime) -> Self {
-        self.modify_event(|event| event.start = Some(start))
+    ///  * `start` - new EventDateTime format or a When
+    pub fn set_start(self, start: impl Into<EventDateTime>) -> Self {
+        self.modify_event(|event| event.start = Some(start.into()))
     }
 
     /// Patch the summary of the event
@@ -641,6 +1039,22 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
         self.modify_event(|event| event.attendees = attendees)
     }
 
+    /// Patch the attendees of the event from their email addresses
+    ///
+    /// # Arguments
+    ///
+    /// * `emails` - &[&str]
+    ///
+    /// Like `set_attendees` this overwrites the existing attendee list.
+    pub fn set_attendees_by_email(self, emails: &[&str]) -> Self {
+        self.set_attendees(
+            emails
+                .iter()
+                .map(|email| EventAttendee::from_email(email))
+                .collect(),
+        )
+    }
+
     /// Patch the color_id of the event
     ///
     /// # Arguments
@@ -650,6 +1064,11 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
         self.modify_event(|event| event.color_id = Some(id.to_string()))
     }
 
+    /// Patch the color of the event, use `set_color_id` for colors that aren't in `EventColor`.
+    pub fn set_color(self, color: EventColor) -> Self {
+        self.set_color_id(color.as_str())
+    }
+
     /// Patch the event type of the event
     ///
     /// # Arguments
@@ -780,10 +1199,16 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
     /// * `status` - &str
     ///
     /// Options are "confirmed", "tentative", "cancelled"
+    #[deprecated(note = "use `set_event_status` with an `EventStatus`")]
     pub fn set_status(self, status: &str) -> Self {
         self.modify_event(|event| event.status = Some(status.to_string()))
     }
 
+    /// Patch the status of the event, cancelling an event this way deletes it.
+    pub fn set_event_status(self, status: EventStatus) -> Self {
+        self.modify_event(|event| event.status = Some(status.as_str().to_string()))
+    }
+
     /// Patch the transparancy field
     ///
     /// # Arguments
@@ -800,11 +1225,17 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
     ///
     /// # Arguments
     ///
-    /// * `visibility` - &str
+    /// * `visibility` - Who can see the details of the event
+    pub fn set_visibility(self, visibility: Visibility) -> Self {
+        self.modify_event(|event| event.visibility = Some(visibility.as_str().to_string()))
+    }
+
+    /// Patch the visibility field
     ///
     /// Options are "default", "public" (all readers of the calendar), "private" (only attendees
     /// may view event details), "confidential" (same as private added compatibility reasons)
-    pub fn set_visibility(self, visibility: &str) -> Self {
+    #[deprecated(note = "use `set_visibility` with a `Visibility`")]
+    pub fn set_visibility_str(self, visibility: &str) -> Self {
         self.modify_event(|event| event.visibility = Some(visibility.to_string()))
     }
 
@@ -821,10 +1252,19 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
     ///
     /// # Arguments
     ///
-    /// * `send` - &str
+    /// * `send` - Guests who should receive notifications about the changes
+    pub fn set_send_updates(mut self, send: SendUpdates) -> Self {
+        self.request
+            .params
+            .insert("sendUpdates".to_string(), send.as_str().to_string());
+        self
+    }
+
+    /// Set the query parameter sendUpdates
     ///
     /// options are "all", "externalOnly", "none"
-    pub fn set_send_updates(mut self, send: &str) -> Self {
+    #[deprecated(note = "use `set_send_updates` with a `SendUpdates`")]
+    pub fn set_send_updates_str(mut self, send: &str) -> Self {
         self.request
             .params
             .insert("sendUpdates".to_string(), send.to_string());
@@ -866,6 +1306,43 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
         self
     }
 
+    /// Replaces everything patched so far with the given patch.
+    pub fn set_event(mut self, event: PatchEventRequest) -> Self {
+        self.event = Some(EventRequest::Patch(event));
+        self
+    }
+
+    /// Patches the event with the writable fields of a fetched event, for read-modify-write:
+    ///
+    /// ```rust
+    /// let mut event = CalendarEventsClient::new(&client)
+    ///     .upcoming("primary", 1)
+    ///     .request()
+    ///     .await?
+    ///     .unwrap()
+    ///     .items
+    ///     .remove(0);
+    /// event.summary = "Moved standup".to_string();
+    /// let (id, etag) = (event.id.clone(), event.etag.clone());
+    /// CalendarEventsClient::new(&client)
+    ///     .patch_event("primary", &id)
+    ///     .from_event(event)
+    ///     .set_if_match(&etag)
+    ///     .request()
+    ///     .await?;
+    /// ```
+    pub fn from_event(self, event: Event) -> Self {
+        self.set_event(event.into())
+    }
+
+    /// Only patches the event when its ETag still matches, e.g. `event.etag` of the fetched event.
+    /// When the event was changed in the meantime the request fails with
+    /// `GoogleApiError::PreconditionFailed` instead of overwriting the other change.
+    pub fn set_if_match(mut self, etag: &str) -> Self {
+        self.request.set_if_match(etag);
+        self
+    }
+
     fn modify_event<F>(mut self, modifier: F) -> Self
     where
         F: FnOnce(&mut PatchEventRequest),
@@ -881,9 +1358,11 @@ impl<'a> CalendarEventsClient<'a, EventPatchMode> {
     /// # Returns
     ///
     /// * `Ok(Some(Event))` - The patched event if successful
-    /// * `Ok(None)` - If the request was unsuccessful
-    /// * `Err` - If there was an error making the request
+    /// * `Err(Error::Api)` - If Google responded with an unsuccessful status code
+    /// * `Err` - If there was another error making the request or both the start and end are
+    ///   patched and the end is before the start
     pub async fn request(&mut self) -> Result<Option<Event>, Error> {
+        self.validate_event_range()?;
         self.make_request().await
     }
 }
@@ -893,13 +1372,17 @@ impl<'a> CalendarEventsClient<'a, EventDeleteMode> {
     ///
     /// # Returns
     ///
-    /// * `Result<bool, Error>` - A result indicating whether the deletion was successful.
+    /// * `Result<(), Error>` - An `Error::Api` when Google didn't delete the event.
     pub async fn request(&mut self) -> Result<(), Error> {
-        match self.make_delete_request().await {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(anyhow::anyhow!("Failed to delete event")),
-            Err(e) => Err(e),
-        }
+        self.make_delete_request().await
+    }
+
+    /// Guests who should receive notifications about the deletion of the event.
+    pub fn send_updates(mut self, send: SendUpdates) -> Self {
+        self.request
+            .params
+            .insert("sendUpdates".to_string(), send.as_str().to_string());
+        self
     }
 
     /// Guests who should receive notifications about the deletion of the event.
@@ -908,10 +1391,74 @@ impl<'a> CalendarEventsClient<'a, EventDeleteMode> {
     /// "all": Notifications are sent to all guests.
     /// "externalOnly": Notifications are sent to non-Google Calendar guests only.
     /// "none": No notifications are sent. For calendar migration tasks, consider using the Events.import method instead.
-    pub fn send_updates(mut self, send: &str) -> Self {
+    #[deprecated(note = "use `send_updates` with a `SendUpdates`")]
+    pub fn send_updates_str(mut self, send: &str) -> Self {
         self.request
             .params
             .insert("sendUpdates".to_string(), send.to_string());
         self
     }
 }
+
+impl<'a> CalendarEventsClient<'a, EventWatchMode> {
+    /// Sets the id of the channel, a UUID or similar unique string.
+    pub fn channel_id(self, id: &str) -> Self {
+        self.modify_watch(|watch| watch.id = id.to_string())
+    }
+
+    /// Sets the HTTPS webhook address notifications are delivered to.
+    pub fn address(self, address: &str) -> Self {
+        self.modify_watch(|watch| watch.address = address.to_string())
+    }
+
+    /// Sets an arbitrary string that is delivered with each notification,
+    /// useful to verify that a notification originates from this channel.
+    pub fn token(self, token: &str) -> Self {
+        self.modify_watch(|watch| watch.token = Some(token.to_string()))
+    }
+
+    /// Sets the time-to-live of the channel in seconds.
+    pub fn ttl(self, seconds: u64) -> Self {
+        self.modify_watch(|watch| {
+            watch.params = Some(WatchParams {
+                ttl: seconds.to_string(),
+            })
+        })
+    }
+
+    fn modify_watch<F>(mut self, modifier: F) -> Self
+    where
+        F: FnOnce(&mut WatchRequest),
+    {
+        if let Some(EventRequest::Watch(ref mut watch)) = self.event {
+            modifier(watch);
+        }
+        self
+    }
+
+    /// Executes the request and returns the created notification channel.
+    pub async fn request(&mut self) -> Result<Option<Channel>, Error> {
+        if let Some(EventRequest::Watch(watch)) = &self.event {
+            if watch.id.is_empty() || watch.address.is_empty() {
+                return Err(Error::InvalidRequest(
+                    "A watch request requires a channel id and an address".to_string(),
+                ));
+            }
+        }
+        self.make_request().await
+    }
+}
+
+impl<'a> CalendarEventsClient<'a, ChannelStopMode> {
+    /// Executes the request to stop the channel.
+    pub async fn request(&mut self) -> Result<(), Error> {
+        let body = serde_json::to_string(&self.event)?;
+        let res = self.request.send(Some(body)).await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(GoogleApiError::from_response(res).await.into())
+        }
+    }
+}
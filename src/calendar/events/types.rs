@@ -1,25 +1,30 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/**
+ * The default reminders on the calendar for the authenticated user. These reminders apply to all events on this calendar that do not explicitly override them (i.e. do not have reminders.useDefault set to True).
+ */
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub struct EventDefaultReminder {
     /**
-     *The default reminders on the calendar for the authenticated user. These reminders apply to all events on this calendar that do not explicitly override them (i.e. do not have reminders.useDefault set to True).
+     * The method used by this reminder. Possible values are: "email", "popup".
      */
-
     #[serde(
         default,
         skip_serializing_if = "String::is_empty",
         deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
     )]
-    method: String,
+    pub method: String,
 
+    /**
+     * Number of minutes before the start of the event when the reminder should trigger.
+     */
     #[serde(
         default,
         skip_serializing_if = "crate::utils::validation::zero_i64",
         deserialize_with = "crate::utils::deserialize::deserialize_nullable_i64::deserialize"
     )]
-    minutes: i64,
+    pub minutes: i64,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -427,6 +432,14 @@ pub struct Event {
         rename = "eventType"
     )]
     pub event_type: String,
+
+    /**
+     * Fields returned by Google that aren't modeled by this struct.
+     * Requires the `capture-extra` feature.
+     */
+    #[cfg(feature = "capture-extra")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -474,7 +487,220 @@ pub struct EventDateTime {
     pub time_zone: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+impl EventDateTime {
+    /// Normalizes the `dateTime` of timed events and the `date` of all-day events to a single
+    /// instant. All-day events resolve to midnight in `time_zone` only when it's a fixed UTC
+    /// offset like "+02:00". Google usually sends IANA names like "Europe/Amsterdam", or no zone
+    /// at all so the calendar's zone applies; those can't be resolved without a time zone
+    /// database and return None, use `to_utc_in` with the zone instead.
+    pub fn to_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        if let Some(date_time) = self.date_time {
+            return Some(date_time);
+        }
+        let offset = self
+            .time_zone
+            .as_deref()
+            .and_then(|tz| tz.parse::<chrono::FixedOffset>().ok())?;
+        self.to_utc_in(&offset)
+    }
+
+    /// Same as `to_utc`, but all-day events resolve to midnight in the given zone, e.g. a
+    /// `chrono_tz::Tz` for the calendar's "Europe/Amsterdam". Timed events ignore the zone.
+    /// Returns None when midnight doesn't exist in the zone because of a DST transition.
+    pub fn to_utc_in<Tz: chrono::TimeZone>(
+        &self,
+        tz: &Tz,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        if let Some(date_time) = self.date_time {
+            return Some(date_time);
+        }
+        let midnight = chrono::NaiveDate::parse_from_str(self.date.as_deref()?, "%Y-%m-%d")
+            .ok()?
+            .and_time(chrono::NaiveTime::MIN);
+        tz.from_local_datetime(&midnight)
+            .earliest()
+            .map(|date_time| date_time.with_timezone(&chrono::Utc))
+    }
+}
+
+impl Event {
+    /// The start of the event regardless of whether it is an all-day or a timed event.
+    /// See `EventDateTime::to_utc` for how all-day events are resolved, they are None unless
+    /// their time zone is a fixed offset.
+    pub fn start_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.start.as_ref().and_then(EventDateTime::to_utc)
+    }
+
+    /// The end of the event regardless of whether it is an all-day or a timed event.
+    /// See `EventDateTime::to_utc` for how all-day events are resolved.
+    pub fn end_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.end.as_ref().and_then(EventDateTime::to_utc)
+    }
+
+    /// Same as `start_datetime`, all-day events start at midnight in the given zone.
+    pub fn start_datetime_in<Tz: chrono::TimeZone>(
+        &self,
+        tz: &Tz,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.start.as_ref().and_then(|start| start.to_utc_in(tz))
+    }
+
+    /// Same as `end_datetime`, all-day events end at midnight in the given zone.
+    pub fn end_datetime_in<Tz: chrono::TimeZone>(
+        &self,
+        tz: &Tz,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.end.as_ref().and_then(|end| end.to_utc_in(tz))
+    }
+
+    /// Whether the event lasts whole days, its start has a `date` instead of a `dateTime`.
+    pub fn is_all_day(&self) -> bool {
+        self.start
+            .as_ref()
+            .is_some_and(|start| start.date.is_some())
+    }
+
+    /// Whether the event starts at a specific time, its start has a `dateTime`.
+    pub fn is_timed(&self) -> bool {
+        self.start
+            .as_ref()
+            .is_some_and(|start| start.date_time.is_some())
+    }
+
+    /// The status of the event, `None` when it's missing and `FetchedEventStatus::Unknown` for a
+    /// status this crate doesn't know yet.
+    pub fn event_status(&self) -> Option<FetchedEventStatus> {
+        let status = match self.status.as_str() {
+            "confirmed" => EventStatus::Confirmed,
+            "tentative" => EventStatus::Tentative,
+            "cancelled" => EventStatus::Cancelled,
+            "" => return None,
+            unknown => return Some(FetchedEventStatus::Unknown(unknown.to_string())),
+        };
+        Some(FetchedEventStatus::Known(status))
+    }
+
+    /// Whether the event was deleted, deleted events are only returned with `show_deleted`.
+    pub fn is_cancelled(&self) -> bool {
+        self.event_status() == Some(FetchedEventStatus::Known(EventStatus::Cancelled))
+    }
+}
+
+/// The status of an event.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum EventStatus {
+    Confirmed,
+    Tentative,
+    Cancelled,
+}
+
+impl EventStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventStatus::Confirmed => "confirmed",
+            EventStatus::Tentative => "tentative",
+            EventStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// The status of an event returned by Google, see `Event::event_status`. Unlike `EventStatus`
+/// it can hold a status this crate doesn't know yet, which is why it can't be set on a request.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum FetchedEventStatus {
+    Known(EventStatus),
+    /// A status this crate doesn't know yet, with the value Google returned.
+    Unknown(String),
+}
+
+/// Who can see the details of an event.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Visibility {
+    /// The default visibility of the calendar.
+    Default,
+    /// All readers of the calendar can see the details.
+    Public,
+    /// Only attendees can see the details.
+    Private,
+    /// Same as private, exists for compatibility reasons.
+    Confidential,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Visibility::Default => "default",
+            Visibility::Public => "public",
+            Visibility::Private => "private",
+            Visibility::Confidential => "confidential",
+        }
+    }
+}
+
+/// The start or end of an event when creating or patching it.
+/// Google expects either a `date` for all-day events or a `dateTime` for timed events, never
+/// both. Converting a `When` into an `EventDateTime` always produces one of the two.
+/// Converting into an `(EventDateTime, EventDateTime)` pair gives the start and end at once,
+/// e.g. `let (start, end) = When::Span(from..to, None).into();`.
+#[derive(PartialEq, Debug, Clone)]
+pub enum When {
+    /// An all-day event on the given date.
+    AllDay(chrono::NaiveDate),
+    /// A timed event at the given instant, optionally with an IANA time zone name like
+    /// "Europe/Amsterdam". The time zone is required for recurring events.
+    At(chrono::DateTime<chrono::Utc>, Option<String>),
+    /// A timed event from the start up to the end of the range, with the time zone of `At`.
+    /// Converted into a single `EventDateTime` it's the start.
+    Span(
+        std::ops::Range<chrono::DateTime<chrono::Utc>>,
+        Option<String>,
+    ),
+}
+
+impl From<When> for EventDateTime {
+    fn from(when: When) -> Self {
+        match when {
+            When::AllDay(date) => EventDateTime {
+                date: Some(date.format("%Y-%m-%d").to_string()),
+                date_time: None,
+                time_zone: None,
+            },
+            When::At(date_time, time_zone) => EventDateTime {
+                date: None,
+                date_time: Some(date_time),
+                time_zone,
+            },
+            When::Span(span, time_zone) => When::At(span.start, time_zone).into(),
+        }
+    }
+}
+
+impl From<When> for (EventDateTime, EventDateTime) {
+    /// The start and end of the event. The end date of an all-day event is exclusive, so
+    /// `AllDay` ends on the next day.
+    fn from(when: When) -> Self {
+        match when {
+            When::AllDay(date) => {
+                let end = date.succ_opt().unwrap_or(date);
+                (When::AllDay(date).into(), When::AllDay(end).into())
+            }
+            When::At(date_time, time_zone) => (
+                When::At(date_time, time_zone.clone()).into(),
+                When::At(date_time, time_zone).into(),
+            ),
+            When::Span(span, time_zone) => (
+                When::At(span.start, time_zone.clone()).into(),
+                When::At(span.end, time_zone).into(),
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct EventAttendee {
     #[serde(
         default,
@@ -534,6 +760,16 @@ pub struct EventAttendee {
     pub additional_guests: i64,
 }
 
+impl EventAttendee {
+    /// Creates an attendee with only an email address, the minimum Google needs to invite someone.
+    pub fn from_email(email: &str) -> Self {
+        Self {
+            email: email.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub struct EventExtendedProperties {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -805,7 +1041,9 @@ pub struct EventSource {
     pub title: String,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+/// Where the user works, for events of type `EventType::WorkingLocation`.
+/// `type` is "homeOffice", "officeLocation" or "customLocation", only the matching field is set.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct WorkingLocationProperties {
     #[serde(
         default,
@@ -836,7 +1074,38 @@ pub struct WorkingLocationProperties {
     pub office_location: Option<OfficeLocation>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+impl WorkingLocationProperties {
+    /// The user works from home.
+    pub fn home_office() -> Self {
+        Self {
+            r#type: "homeOffice".to_string(),
+            home_office: Some(serde_json::json!({})),
+            ..Default::default()
+        }
+    }
+
+    /// The user works from an office, see `OfficeLocation`.
+    pub fn office_location(location: OfficeLocation) -> Self {
+        Self {
+            r#type: "officeLocation".to_string(),
+            office_location: Some(location),
+            ..Default::default()
+        }
+    }
+
+    /// The user works from another location, e.g. "Client site".
+    pub fn custom_location(label: &str) -> Self {
+        Self {
+            r#type: "customLocation".to_string(),
+            custom_location: Some(CustomLocation {
+                label: label.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct CustomLocation {
     #[serde(
         default,
@@ -846,7 +1115,7 @@ pub struct CustomLocation {
     pub label: String,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct OfficeLocation {
     #[serde(
         default,
@@ -1001,10 +1270,10 @@ pub struct BirthdayProperties {
     pub custom_type_name: String,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct EventList {
     /**
-     * ETag of the collection.
+     * The user's access role for this calendar. Possible values are: "none", "freeBusyReader", "reader", "writer", "owner".
      */
     #[serde(
         default,
@@ -1024,7 +1293,7 @@ pub struct EventList {
     )]
     pub default_reminders: Vec<EventDefaultReminder>,
     /**
-     * ETag of the collection.
+     * Description of the calendar.
      */
     #[serde(
         default,
@@ -1051,7 +1320,7 @@ pub struct EventList {
     )]
     pub items: Vec<Event>,
     /**
-     * ETag of the collection.
+     * Type of the collection. Always calendar#events.
      */
     #[serde(
         default,
@@ -1060,7 +1329,7 @@ pub struct EventList {
     )]
     pub kind: String,
     /**
-     * ETag of the collection.
+     * Token used to access the next page of this result. Omitted if no further results are available.
      */
     #[serde(
         default,
@@ -1070,7 +1339,7 @@ pub struct EventList {
     )]
     pub next_page_token: String,
     /**
-     * ETag of the collection.
+     * Token used at a later point in time to retrieve only the entries that have changed since this result was returned.
      */
     #[serde(
         default,
@@ -1080,7 +1349,7 @@ pub struct EventList {
     )]
     pub next_sync_token: String,
     /**
-     * ETag of the collection.
+     * Title of the calendar.
      */
     #[serde(
         default,
@@ -1089,7 +1358,7 @@ pub struct EventList {
     )]
     pub summary: String,
     /**
-     * ETag of the collection.
+     * The time zone of the calendar.
      */
     #[serde(
         default,
@@ -1099,7 +1368,7 @@ pub struct EventList {
     )]
     pub time_zone: String,
     /**
-     * Last modification time of the color palette (as a RFC3339 timestamp). Read-only.
+     * Last modification time of the calendar (as a RFC3339 timestamp). Read-only.
      */
     #[serde(
         default,
@@ -1110,7 +1379,24 @@ pub struct EventList {
     pub updated: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl EventList {
+    /// The all-day events of the list, Google has no filter for these.
+    pub fn all_day_events(&self) -> Vec<&Event> {
+        self.items
+            .iter()
+            .filter(|event| event.is_all_day())
+            .collect()
+    }
+
+    /// The events of the list that start at a specific time. Events without a start, like
+    /// cancelled occurrences, are in neither this list nor `all_day_events`.
+    pub fn timed_events(&self) -> Vec<&Event> {
+        self.items.iter().filter(|event| event.is_timed()).collect()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct PatchEventRequest {
     /// The (exclusive) end time of the event
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1222,6 +1508,7 @@ pub struct PatchEventRequest {
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateEventRequest {
     /// Required: The (exclusive) end time of the event
     pub end: EventDateTime,
@@ -1331,6 +1618,7 @@ pub struct CreateEventRequest {
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ConferenceData {
     /// Conference solution
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1352,6 +1640,51 @@ pub struct ExtendedProperties {
     pub shared: Option<std::collections::HashMap<String, String>>,
 }
 
+/// Copies the writable fields of a fetched event, so it can be modified and written back.
+/// Empty fields are left out of the patch. The sequence isn't copied, Google increments it itself.
+impl From<Event> for PatchEventRequest {
+    fn from(event: Event) -> Self {
+        let non_empty = |value: String| (!value.is_empty()).then_some(value);
+        PatchEventRequest {
+            end: event.end,
+            start: event.start,
+            anyone_can_add_self: event.anyone_can_add_self,
+            attendees: event.attendees,
+            birthday_properties: event.birthday_properties,
+            color_id: non_empty(event.color_id),
+            conference_data: event.conference_data.map(|data| ConferenceData {
+                conference_solution: data.conference_solution,
+                entry_points: data.entry_points,
+            }),
+            description: non_empty(event.description),
+            event_type: non_empty(event.event_type),
+            extended_properties: event
+                .extended_properties
+                .map(|properties| ExtendedProperties {
+                    private: properties.private,
+                    shared: properties.shared,
+                }),
+            focus_time_properties: event.focus_time_properties,
+            gadget: event.gadget,
+            guests_can_invite_others: event.guests_can_invite_others,
+            guests_can_modify: event.guests_can_modify,
+            guests_can_see_other_guests: event.guests_can_see_other_guests,
+            id: non_empty(event.id),
+            location: non_empty(event.location),
+            out_of_office_properties: event.out_of_office_properties,
+            recurrence: event.recurrence,
+            reminders: event.reminders,
+            sequence: None,
+            source: event.source,
+            status: non_empty(event.status),
+            summary: non_empty(event.summary),
+            transparency: non_empty(event.transparency),
+            visibility: non_empty(event.visibility),
+            working_location_properties: event.working_location_properties,
+        }
+    }
+}
+
 impl CreateEventRequest {
     /// Creates a new instance of `ExtendedProperties` with empty maps
     pub fn new(start: EventDateTime, end: EventDateTime) -> Self {
@@ -1386,3 +1719,93 @@ impl CreateEventRequest {
         }
     }
 }
+
+/**
+ * A notification channel used to watch for changes to a resource.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Channel {
+    /**
+     * A UUID or similar unique string that identifies this channel.
+     */
+    #[serde(
+        default,
+        skip_serializing_if = "String::is_empty",
+        deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
+    )]
+    pub id: String,
+    /**
+     * An opaque ID that identifies the resource being watched on this channel.
+     * Required together with the id to stop the channel.
+     */
+    #[serde(
+        default,
+        skip_serializing_if = "String::is_empty",
+        deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
+    )]
+    pub resource_id: String,
+    /**
+     * A version-specific identifier for the watched resource.
+     */
+    #[serde(
+        default,
+        skip_serializing_if = "String::is_empty",
+        deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
+    )]
+    pub resource_uri: String,
+    /**
+     * An arbitrary string delivered to the target address with each notification.
+     */
+    #[serde(
+        default,
+        skip_serializing_if = "String::is_empty",
+        deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
+    )]
+    pub token: String,
+    /**
+     * Date and time of notification channel expiration, expressed as a Unix timestamp, in milliseconds.
+     */
+    #[serde(
+        default,
+        skip_serializing_if = "String::is_empty",
+        deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
+    )]
+    pub expiration: String,
+}
+
+impl Channel {
+    /// Returns the expiration of the channel, or None if Google did not return a valid timestamp.
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let millis = self.expiration.parse::<i64>().ok()?;
+        chrono::DateTime::from_timestamp_millis(millis)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchRequest {
+    /// A UUID or similar unique string that identifies this channel
+    pub id: String,
+
+    /// The type of delivery mechanism used for this channel, always "web_hook"
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// The address where notifications are delivered for this channel
+    pub address: String,
+
+    /// An arbitrary string delivered to the target address with each notification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Additional parameters controlling delivery channel behavior
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<WatchParams>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct WatchParams {
+    /// The time-to-live in seconds for the notification channel, Google defaults to 604800 seconds
+    pub ttl: String,
+}
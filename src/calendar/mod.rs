@@ -1,9 +1,18 @@
 pub mod events;
 pub mod requests;
 pub mod prelude {
+    pub use crate::calendar::events::requests::EventColor;
     pub use crate::calendar::events::requests::EventOrderBy;
     pub use crate::calendar::events::requests::EventType;
+    pub use crate::calendar::events::requests::SendUpdates;
+    pub use crate::calendar::events::types::Channel;
+    pub use crate::calendar::events::types::EventStatus;
+    pub use crate::calendar::events::types::FetchedEventStatus;
+    pub use crate::calendar::events::types::Visibility;
+    pub use crate::calendar::events::types::When;
+    pub use crate::utils::request::HeaderRequestTrait;
     pub use crate::utils::request::PaginationRequestTrait;
+    pub use crate::utils::request::RawParamRequestTrait;
     pub use crate::utils::request::TimeRequestTrait;
 }
 pub mod types;
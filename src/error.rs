@@ -0,0 +1,194 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+
+/// Response headers Google uses to identify a request, in order of preference.
+const REQUEST_ID_HEADERS: [&str; 3] = ["x-goog-request-id", "x-guploader-uploadid", "x-request-id"];
+
+/// Error returned when a Google API responds with an unsuccessful status code.
+/// The `request_id` is taken from the response headers when Google includes one,
+/// Google support asks for it when debugging a failing request.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum GoogleApiError {
+    /// The access token was rejected (401).
+    Unauthorized {
+        message: String,
+        request_id: Option<String>,
+    },
+    /// The resource changed since the ETag sent with `If-Match` (412), see `set_if_match`
+    /// on the patch builders. Fetch the resource again and reapply the change.
+    PreconditionFailed {
+        message: String,
+        request_id: Option<String>,
+    },
+    /// Any other unsuccessful response.
+    Status {
+        status: StatusCode,
+        message: String,
+        request_id: Option<String>,
+    },
+}
+
+impl GoogleApiError {
+    /// Builds the error from an unsuccessful response.
+    /// Google wraps errors as `{"error": {"code": 404, "message": "Not Found"}}`,
+    /// when the body has that shape the message is extracted, otherwise the raw body is used.
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let request_id = REQUEST_ID_HEADERS.iter().find_map(|header| {
+            response
+                .headers()
+                .get(*header)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        });
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json["error"]["message"].as_str().map(str::to_string))
+            .unwrap_or(body);
+
+        match status {
+            StatusCode::UNAUTHORIZED => GoogleApiError::Unauthorized {
+                message,
+                request_id,
+            },
+            StatusCode::PRECONDITION_FAILED => GoogleApiError::PreconditionFailed {
+                message,
+                request_id,
+            },
+            _ => GoogleApiError::Status {
+                status,
+                message,
+                request_id,
+            },
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            GoogleApiError::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            GoogleApiError::PreconditionFailed { .. } => StatusCode::PRECONDITION_FAILED,
+            GoogleApiError::Status { status, .. } => *status,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            GoogleApiError::Unauthorized { message, .. } => message,
+            GoogleApiError::PreconditionFailed { message, .. } => message,
+            GoogleApiError::Status { message, .. } => message,
+        }
+    }
+
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            GoogleApiError::Unauthorized { request_id, .. } => request_id.as_deref(),
+            GoogleApiError::PreconditionFailed { request_id, .. } => request_id.as_deref(),
+            GoogleApiError::Status { request_id, .. } => request_id.as_deref(),
+        }
+    }
+}
+
+impl fmt::Display for GoogleApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Google API error {}: {}", self.status(), self.message())?;
+        if let Some(request_id) = self.request_id() {
+            write!(f, " (request id {request_id})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GoogleApiError {}
+
+/// Crate-wide result type, see `Error`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error returned by all fallible functions in this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The HTTP request failed before a response was received, or the response body couldn't be read.
+    Http(reqwest::Error),
+    /// A request body couldn't be serialized or a response body couldn't be deserialized.
+    Json(serde_json::Error),
+    /// A credentials or key file couldn't be read.
+    Io(std::io::Error),
+    /// Authentication failed, for example because the client has no token,
+    /// the credentials are incomplete or a token couldn't be retrieved.
+    Auth(String),
+    /// Google responded with an unsuccessful status code.
+    Api(GoogleApiError),
+    /// The request was rejected before it was sent because it is invalid.
+    InvalidRequest(String),
+    /// The request failed without a more specific error.
+    Request(String),
+    /// The resource didn't change since the ETag sent with `If-None-Match`,
+    /// see `HeaderRequestTrait::conditional`.
+    NotModified,
+}
+
+impl Error {
+    /// Returns the Google API error when Google responded with an unsuccessful status code.
+    pub fn api_error(&self) -> Option<&GoogleApiError> {
+        match self {
+            Error::Api(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(error) => write!(f, "HTTP error: {error}"),
+            Error::Json(error) => write!(f, "JSON error: {error}"),
+            Error::Io(error) => write!(f, "IO error: {error}"),
+            Error::Auth(message) => write!(f, "Authentication error: {message}"),
+            Error::Api(error) => error.fmt(f),
+            Error::InvalidRequest(message) => write!(f, "{message}"),
+            Error::Request(message) => write!(f, "{message}"),
+            Error::NotModified => write!(f, "Resource not modified"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(error) => Some(error),
+            Error::Json(error) => Some(error),
+            Error::Io(error) => Some(error),
+            Error::Api(error) => Some(error),
+            Error::Auth(_) | Error::InvalidRequest(_) | Error::Request(_) | Error::NotModified => {
+                None
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Http(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Json(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<GoogleApiError> for Error {
+    fn from(error: GoogleApiError) -> Self {
+        Error::Api(error)
+    }
+}
@@ -0,0 +1,250 @@
+use base64::Engine;
+
+/// Builds RFC 2822 messages for `send_message` and `insert_message`.
+///
+/// # Examples
+///
+/// ```rust
+/// let raw = MimeMessageBuilder::new()
+///     .to("someone@example.com")
+///     .subject("Report")
+///     .text_body("The report is attached")
+///     .attachment("report.pdf", "application/pdf", pdf_bytes)
+///     .build_raw();
+///
+/// let sent = GmailClient::new(client)
+///     .send_message("me")
+///     .set_raw(&raw)
+///     .request()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MimeMessageBuilder {
+    from: Option<String>,
+    to: Vec<String>,
+    cc: Vec<String>,
+    bcc: Vec<String>,
+    subject: Option<String>,
+    in_reply_to: Option<String>,
+    references: Vec<String>,
+    text_body: Option<String>,
+    html_body: Option<String>,
+    attachments: Vec<MimeAttachment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MimeAttachment {
+    filename: String,
+    mime_type: String,
+    data: Vec<u8>,
+}
+
+const MIXED_BOUNDARY: &str = "=_google_workspace_apis_mixed";
+const ALTERNATIVE_BOUNDARY: &str = "=_google_workspace_apis_alternative";
+
+impl MimeMessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the sender, Gmail uses the authenticated user when this isn't set.
+    pub fn from(mut self, address: &str) -> Self {
+        self.from = Some(address.to_string());
+        self
+    }
+
+    /// Adds a recipient, can be called multiple times.
+    pub fn to(mut self, address: &str) -> Self {
+        self.to.push(address.to_string());
+        self
+    }
+
+    /// Adds a carbon copy recipient, can be called multiple times.
+    pub fn cc(mut self, address: &str) -> Self {
+        self.cc.push(address.to_string());
+        self
+    }
+
+    /// Adds a blind carbon copy recipient, can be called multiple times.
+    pub fn bcc(mut self, address: &str) -> Self {
+        self.bcc.push(address.to_string());
+        self
+    }
+
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    /// Sets the `Message-ID` header of the message this is a reply to, e.g. `<abc@mail.gmail.com>`.
+    /// Gmail only threads the reply when the `thread_id` of the request is set as well,
+    /// see `GmailClient::send_message`.
+    pub fn in_reply_to(mut self, message_id: &str) -> Self {
+        self.in_reply_to = Some(message_id.to_string());
+        self
+    }
+
+    /// Sets the `Message-ID` headers of the previous messages in the thread,
+    /// usually the `References` of the message replied to followed by its `Message-ID`.
+    pub fn references(mut self, message_ids: &[&str]) -> Self {
+        self.references = message_ids.iter().map(|id| id.to_string()).collect();
+        self
+    }
+
+    /// Sets the plain text body. When an html body is set as well both are sent as alternatives.
+    pub fn text_body(mut self, body: &str) -> Self {
+        self.text_body = Some(body.to_string());
+        self
+    }
+
+    pub fn html_body(mut self, body: &str) -> Self {
+        self.html_body = Some(body.to_string());
+        self
+    }
+
+    /// Adds an attachment, e.g. `attachment("report.pdf", "application/pdf", bytes)`.
+    pub fn attachment(mut self, filename: &str, mime_type: &str, data: impl Into<Vec<u8>>) -> Self {
+        self.attachments.push(MimeAttachment {
+            filename: filename.to_string(),
+            mime_type: mime_type.to_string(),
+            data: data.into(),
+        });
+        self
+    }
+
+    /// Returns the message in RFC 2822 format.
+    pub fn build(&self) -> String {
+        let mut message = String::new();
+        if let Some(from) = &self.from {
+            push_header(&mut message, "From", from);
+        }
+        for (name, addresses) in [("To", &self.to), ("Cc", &self.cc), ("Bcc", &self.bcc)] {
+            if !addresses.is_empty() {
+                push_header(&mut message, name, &addresses.join(", "));
+            }
+        }
+        if let Some(subject) = &self.subject {
+            push_header(&mut message, "Subject", &encode_header_value(subject));
+        }
+        if let Some(in_reply_to) = &self.in_reply_to {
+            push_header(&mut message, "In-Reply-To", in_reply_to);
+        }
+        if !self.references.is_empty() {
+            push_header(&mut message, "References", &self.references.join(" "));
+        }
+        push_header(&mut message, "MIME-Version", "1.0");
+
+        if self.attachments.is_empty() {
+            message.push_str(&self.body_part());
+            return message;
+        }
+
+        push_header(
+            &mut message,
+            "Content-Type",
+            &format!("multipart/mixed; boundary=\"{MIXED_BOUNDARY}\""),
+        );
+        message.push_str("\r\n");
+        message.push_str(&format!("--{MIXED_BOUNDARY}\r\n"));
+        message.push_str(&self.body_part());
+        for attachment in &self.attachments {
+            message.push_str(&format!("\r\n--{MIXED_BOUNDARY}\r\n"));
+            let filename = sanitize_header_value(&attachment.filename).replace('"', "");
+            push_header(
+                &mut message,
+                "Content-Type",
+                &format!(
+                    "{}; name=\"{filename}\"",
+                    sanitize_header_value(&attachment.mime_type)
+                ),
+            );
+            push_header(
+                &mut message,
+                "Content-Disposition",
+                &format!("attachment; filename=\"{filename}\""),
+            );
+            push_header(&mut message, "Content-Transfer-Encoding", "base64");
+            message.push_str("\r\n");
+            message.push_str(&encode_base64_lines(&attachment.data));
+        }
+        message.push_str(&format!("\r\n--{MIXED_BOUNDARY}--\r\n"));
+        message
+    }
+
+    /// Returns the message base64url encoded, as expected by `set_raw`.
+    pub fn build_raw(&self) -> String {
+        base64::engine::general_purpose::URL_SAFE.encode(self.build())
+    }
+
+    /// The headers and content of the text and/or html body.
+    fn body_part(&self) -> String {
+        let mut part = String::new();
+        match (&self.text_body, &self.html_body) {
+            (Some(text), Some(html)) => {
+                push_header(
+                    &mut part,
+                    "Content-Type",
+                    &format!("multipart/alternative; boundary=\"{ALTERNATIVE_BOUNDARY}\""),
+                );
+                part.push_str("\r\n");
+                part.push_str(&format!("--{ALTERNATIVE_BOUNDARY}\r\n"));
+                part.push_str(&text_part("text/plain", text));
+                part.push_str(&format!("\r\n--{ALTERNATIVE_BOUNDARY}\r\n"));
+                part.push_str(&text_part("text/html", html));
+                part.push_str(&format!("\r\n--{ALTERNATIVE_BOUNDARY}--\r\n"));
+            }
+            (None, Some(html)) => part.push_str(&text_part("text/html", html)),
+            (text, None) => part.push_str(&text_part(
+                "text/plain",
+                text.as_deref().unwrap_or_default(),
+            )),
+        }
+        part
+    }
+}
+
+fn text_part(mime_type: &str, content: &str) -> String {
+    let mut part = String::new();
+    push_header(
+        &mut part,
+        "Content-Type",
+        &format!("{mime_type}; charset=\"UTF-8\""),
+    );
+    push_header(&mut part, "Content-Transfer-Encoding", "base64");
+    part.push_str("\r\n");
+    part.push_str(&encode_base64_lines(content.as_bytes()));
+    part
+}
+
+fn push_header(message: &mut String, name: &str, value: &str) {
+    message.push_str(&format!("{name}: {}\r\n", sanitize_header_value(value)));
+}
+
+/// Line breaks in a header value would start a new header.
+fn sanitize_header_value(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// Non-ASCII header values are encoded as RFC 2047 encoded word.
+fn encode_header_value(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!(
+            "=?UTF-8?B?{}?=",
+            base64::engine::general_purpose::STANDARD.encode(value)
+        )
+    }
+}
+
+/// Base64 with lines of at most 76 characters as required by RFC 2045.
+fn encode_base64_lines(data: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    let mut lines = String::with_capacity(encoded.len() + encoded.len() / 76 * 2 + 2);
+    for chunk in encoded.as_bytes().chunks(76) {
+        // Base64 is ASCII, so the chunks are valid UTF-8
+        lines.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        lines.push_str("\r\n");
+    }
+    lines
+}
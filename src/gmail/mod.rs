@@ -1,2 +1,12 @@
+pub mod mime;
+#[cfg(test)]
+mod request_tests;
 pub mod requests;
+pub mod prelude {
+    pub use crate::gmail::mime::MimeMessageBuilder;
+    pub use crate::gmail::requests::{BatchOutcome, GmailClient, MAX_INLINE_MESSAGE_SIZE};
+    pub use crate::gmail::types::{InternalDateSource, LabelId, Message, MessageList, SystemLabel};
+    pub use crate::utils::request::HeaderRequestTrait;
+    pub use crate::utils::request::RawParamRequestTrait;
+}
 pub mod types;
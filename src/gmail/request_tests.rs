@@ -0,0 +1,467 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::auth::client::{GoogleClient, JSON_CONTENT_TYPE};
+
+use super::requests::{GmailClient, MAX_INLINE_MESSAGE_SIZE};
+use super::types::{InternalDateSource, LabelId, SystemLabel};
+
+fn dummy_google_client_valid() -> GoogleClient {
+    GoogleClient::mock()
+}
+
+#[test]
+fn small_messages_are_sent_inline() {
+    let gc = dummy_google_client_valid();
+    let mut client = GmailClient::new(&gc)
+        .send_message("me")
+        .set_mime("To: a@example.com\r\nSubject: Hi\r\n\r\nHello");
+
+    assert!(client.prepare_upload().unwrap().is_none());
+    assert_eq!(
+        client.request.url,
+        "https://gmail.googleapis.com/gmail/v1/users/me/messages/send"
+    );
+    assert_eq!(client.request.content_type, JSON_CONTENT_TYPE);
+}
+
+#[test]
+fn large_messages_are_sent_as_multipart_upload() {
+    let gc = dummy_google_client_valid();
+    let mime = format!(
+        "To: a@example.com\r\nSubject: Report\r\n\r\n{}",
+        "a".repeat(MAX_INLINE_MESSAGE_SIZE)
+    );
+    let mut client = GmailClient::new(&gc)
+        .send_message("me")
+        .set_mime(&mime)
+        .set_thread_id("thread-1");
+
+    let body = String::from_utf8(client.prepare_upload().unwrap().unwrap()).unwrap();
+    assert_eq!(
+        client.request.url,
+        "https://gmail.googleapis.com/upload/gmail/v1/users/me/messages/send"
+    );
+    assert_eq!(
+        client.request.params.get("uploadType"),
+        Some(&"multipart".to_string())
+    );
+    let boundary = client
+        .request
+        .content_type
+        .strip_prefix("multipart/related; boundary=")
+        .unwrap()
+        .to_string();
+    assert!(body.starts_with(&format!(
+        "--{boundary}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{{\"threadId\":\"thread-1\"}}\r\n"
+    )));
+    assert!(body.contains(&format!(
+        "--{boundary}\r\nContent-Type: message/rfc822\r\n\r\n{mime}\r\n"
+    )));
+    assert!(body.ends_with(&format!("--{boundary}--")));
+}
+
+#[test]
+fn large_binary_messages_are_uploaded_byte_for_byte() {
+    use base64::Engine;
+
+    let gc = dummy_google_client_valid();
+    let mut mime = b"To: a@example.com\r\nContent-Transfer-Encoding: binary\r\n\r\n".to_vec();
+    mime.extend((0..=255u8).cycle().take(MAX_INLINE_MESSAGE_SIZE));
+    let mut client = GmailClient::new(&gc)
+        .send_message("me")
+        .set_raw(&base64::engine::general_purpose::URL_SAFE.encode(&mime));
+
+    let body = client.prepare_upload().unwrap().unwrap();
+    assert!(String::from_utf8(body.clone()).is_err());
+    assert!(body
+        .windows(mime.len())
+        .any(|window| window == mime.as_slice()));
+}
+
+#[test]
+fn mime_builder_builds_multipart_messages_with_attachments() {
+    use super::mime::MimeMessageBuilder;
+    use base64::Engine;
+
+    let builder = MimeMessageBuilder::new()
+        .to("a@example.com")
+        .to("b@example.com")
+        .cc("c@example.com")
+        .subject("Réunion\r\nBcc: evil@example.com")
+        .text_body("Hello")
+        .html_body("<p>Hello</p>")
+        .attachment("report.pdf", "application/pdf", vec![0u8, 1, 2]);
+    let mime = builder.build();
+
+    assert!(mime.starts_with("To: a@example.com, b@example.com\r\nCc: c@example.com\r\n"));
+    // Subjects with non-ASCII characters are encoded, which also keeps line breaks out of the headers
+    assert!(!mime.contains("\r\nBcc:"));
+    assert!(mime.contains("Subject: =?UTF-8?B?"));
+    assert!(mime.contains("multipart/mixed;"));
+    assert!(mime.contains("multipart/alternative;"));
+    assert!(mime.contains("Content-Type: text/plain; charset=\"UTF-8\""));
+    assert!(mime.contains("Content-Type: text/html; charset=\"UTF-8\""));
+    assert!(mime.contains(
+        "Content-Type: application/pdf; name=\"report.pdf\"\r\n\
+         Content-Disposition: attachment; filename=\"report.pdf\"\r\n\
+         Content-Transfer-Encoding: base64\r\n\r\nAAEC\r\n"
+    ));
+    assert!(mime.ends_with("--\r\n"));
+
+    let raw = base64::engine::general_purpose::URL_SAFE
+        .decode(builder.build_raw())
+        .unwrap();
+    assert_eq!(String::from_utf8(raw).unwrap(), mime);
+}
+
+#[test]
+fn mime_builder_builds_plain_text_messages() {
+    use super::mime::MimeMessageBuilder;
+
+    let mime = MimeMessageBuilder::new()
+        .from("me@example.com")
+        .to("a@example.com")
+        .subject("Hi")
+        .text_body("Hello")
+        .build();
+    assert_eq!(
+        mime,
+        "From: me@example.com\r\nTo: a@example.com\r\nSubject: Hi\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=\"UTF-8\"\r\nContent-Transfer-Encoding: base64\r\n\r\n\
+         SGVsbG8=\r\n"
+    );
+}
+
+#[test]
+fn replies_carry_threading_headers_and_thread_id() {
+    use super::mime::MimeMessageBuilder;
+
+    let raw = MimeMessageBuilder::new()
+        .to("a@example.com")
+        .subject("Re: Plans")
+        .in_reply_to("<b@mail.gmail.com>")
+        .references(&["<a@mail.gmail.com>", "<b@mail.gmail.com>"])
+        .text_body("Sounds good")
+        .build();
+    assert!(raw.contains(
+        "In-Reply-To: <b@mail.gmail.com>\r\nReferences: <a@mail.gmail.com> <b@mail.gmail.com>\r\n"
+    ));
+
+    let gc = dummy_google_client_valid();
+    let client = GmailClient::new(&gc)
+        .send_message("me")
+        .set_mime(&raw)
+        .set_thread_id("thread-1");
+    let body = serde_json::to_value(client.message.as_ref().unwrap()).unwrap();
+    assert_eq!(body["threadId"], "thread-1");
+}
+
+#[test]
+fn message_list_reports_more_pages() {
+    use super::types::MessageList;
+
+    let list: MessageList = serde_json::from_str(
+        r#"{"messages":[{"id":"1","threadId":"t"}],"nextPageToken":"abc","resultSizeEstimate":"201"}"#,
+    )
+    .unwrap();
+    assert!(list.has_more());
+    assert_eq!(list.result_size_estimate, 201);
+
+    let last: MessageList = serde_json::from_str(r#"{"resultSizeEstimate":0}"#).unwrap();
+    assert!(!last.has_more());
+}
+
+#[test]
+fn full_message_listing_keeps_the_list_filters() {
+    let gc = dummy_google_client_valid();
+    let client = GmailClient::new(&gc)
+        .get_emails("me")
+        .max_results(5)
+        .query("is:unread")
+        .with_full_messages();
+    assert_eq!(
+        client.request.url,
+        "https://gmail.googleapis.com/gmail/v1/users/me/messages"
+    );
+    assert_eq!(
+        client.request.params.get("maxResults"),
+        Some(&"5".to_string())
+    );
+    assert_eq!(
+        client.request.params.get("q"),
+        Some(&"is:unread".to_string())
+    );
+}
+
+#[test]
+fn post_without_a_body_object_has_no_body() {
+    let gc = dummy_google_client_valid();
+    let mut builder = GmailClient::new(&gc).modify_email("me", "msg");
+    builder.labels = None;
+    assert_eq!(builder.dry_run().unwrap().body, None);
+
+    let builder = GmailClient::new(&gc).batch_delete("me", vec!["a".to_string()]);
+    assert_eq!(
+        builder.dry_run().unwrap().body.as_deref(),
+        Some(r#"{"ids":["a"]}"#)
+    );
+}
+
+#[tokio::test]
+async fn batch_delete_outcome_is_reported_per_id() {
+    let mut server = mockito::Server::new_async().await;
+    let deleted = server
+        .mock("DELETE", "/messages/a")
+        .with_status(204)
+        .create_async()
+        .await;
+    let unknown = server
+        .mock("DELETE", "/messages/b")
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Not Found"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder =
+        GmailClient::new(&gc).batch_delete("me", vec!["a".to_string(), "b".to_string()]);
+    builder.request.url = format!("{}/messages/batchDelete", server.url());
+    let outcome = builder.request_per_id().await.unwrap();
+    deleted.assert_async().await;
+    unknown.assert_async().await;
+
+    let ids: Vec<_> = outcome.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["a", "b"]);
+    assert!(outcome[0].1.is_ok());
+    let err = outcome[1].1.as_ref().unwrap_err();
+    assert_eq!(err.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn batch_modify_outcome_is_reported_per_id() {
+    let mut server = mockito::Server::new_async().await;
+    let body = r#"{"removeLabelIds":["UNREAD"]}"#;
+    let modified = server
+        .mock("POST", "/messages/a/modify")
+        .match_body(mockito::Matcher::Exact(body.to_string()))
+        .with_status(200)
+        .with_body(r#"{"id": "a"}"#)
+        .create_async()
+        .await;
+    let unknown = server
+        .mock("POST", "/messages/b/modify")
+        .match_body(mockito::Matcher::Exact(body.to_string()))
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Not Found"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder = GmailClient::new(&gc)
+        .batch_modify("me", vec!["a".to_string(), "b".to_string()])
+        .remove_label(SystemLabel::Unread);
+    builder.request.url = format!("{}/messages/batchModify", server.url());
+    let outcome = builder.request_per_id().await.unwrap();
+    modified.assert_async().await;
+    unknown.assert_async().await;
+
+    assert!(outcome[0].1.is_ok());
+    assert_eq!(
+        outcome[1].1.as_ref().unwrap_err().status(),
+        reqwest::StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn unsuccessful_response_is_an_api_error() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/message")
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Not Found"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder = GmailClient::new(&gc).get_email("me", "missing");
+    builder.request.url = format!("{}/message", server.url());
+    let err = builder.request().await.unwrap_err();
+
+    let api_error = err.api_error().unwrap();
+    assert_eq!(api_error.status(), reqwest::StatusCode::NOT_FOUND);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn messages_collect_up_to_limits_the_last_page_and_resumes() {
+    let mut server = mockito::Server::new_async().await;
+    let second = server
+        .mock("GET", "/gmail/v1/users/me/messages")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("pageToken".into(), "p2".into()),
+            mockito::Matcher::UrlEncoded("maxResults".into(), "1".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"messages": [{"id": "c"}], "nextPageToken": "p3"}"#)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/gmail/v1/users/me/messages")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "maxResults".into(),
+            "2".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"messages": [{"id": "a"}, {"id": "b"}], "nextPageToken": "p2"}"#)
+        .create_async()
+        .await;
+
+    let mut gc = dummy_google_client_valid();
+    gc.set_base_url(&server.url());
+    let list = GmailClient::new(&gc)
+        .get_emails("me")
+        .max_results(2)
+        .collect_up_to(Some(3))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = list
+        .messages
+        .iter()
+        .map(|message| message.id.as_str())
+        .collect();
+    assert_eq!(ids, ["a", "b", "c"]);
+    assert_eq!(list.next_page_token, "p3");
+    first.assert_async().await;
+    second.assert_async().await;
+
+    let resumed = GmailClient::new(&gc)
+        .get_emails("me")
+        .page_token(3)
+        .collect_up_to(Some(0))
+        .await
+        .unwrap();
+    assert_eq!(resumed.next_page_token, "3");
+}
+
+#[tokio::test]
+async fn full_messages_fail_when_one_message_fails() {
+    let mut server = mockito::Server::new_async().await;
+    let list = server
+        .mock("GET", "/gmail/v1/users/me/messages")
+        .with_status(200)
+        .with_body(r#"{"messages": [{"id": "a"}, {"id": "gone"}, {"id": "c"}]}"#)
+        .create_async()
+        .await;
+    let found = server
+        .mock(
+            "GET",
+            mockito::Matcher::Regex("^/gmail/v1/users/me/messages/(a|c)$".to_string()),
+        )
+        .with_status(200)
+        .with_body(r#"{"id": "a"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let gone = server
+        .mock("GET", "/gmail/v1/users/me/messages/gone")
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Not Found"}}"#)
+        .create_async()
+        .await;
+
+    let mut gc = dummy_google_client_valid();
+    gc.set_base_url(&server.url());
+    let err = GmailClient::new(&gc)
+        .get_emails("me")
+        .with_full_messages()
+        .request()
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.api_error().unwrap().status(),
+        reqwest::StatusCode::NOT_FOUND
+    );
+    list.assert_async().await;
+    found.assert_async().await;
+    gone.assert_async().await;
+}
+
+#[test]
+fn label_ids_round_trip_through_their_string_ids() {
+    let system_labels = [
+        SystemLabel::Inbox,
+        SystemLabel::Spam,
+        SystemLabel::Trash,
+        SystemLabel::Unread,
+        SystemLabel::Starred,
+        SystemLabel::Important,
+        SystemLabel::Sent,
+        SystemLabel::Draft,
+        SystemLabel::Chat,
+        SystemLabel::CategoryPersonal,
+        SystemLabel::CategorySocial,
+        SystemLabel::CategoryPromotions,
+        SystemLabel::CategoryUpdates,
+        SystemLabel::CategoryForums,
+    ];
+    for label in system_labels {
+        assert_eq!(SystemLabel::from_id(label.as_str()), Some(label));
+        let id = LabelId::from(label.as_str());
+        assert_eq!(id, LabelId::System(label));
+        assert_eq!(id.as_str(), label.as_str());
+    }
+
+    assert_eq!(SystemLabel::from_id("Label_123"), None);
+    let user = LabelId::from("Label_123");
+    assert_eq!(user, LabelId::User("Label_123".to_string()));
+    assert_eq!(user.as_str(), "Label_123");
+    assert_eq!(LabelId::from(user.as_str().to_string()), user);
+}
+
+#[test]
+fn insert_message_dry_run_posts_the_message() {
+    let gc = dummy_google_client_valid();
+    let prepared = GmailClient::new(&gc)
+        .insert_message("me")
+        .set_raw("VG86IGFAZXhhbXBsZS5jb20")
+        .set_thread_id("thread-1")
+        .internal_date_source(InternalDateSource::DateHeader)
+        .add_label(SystemLabel::Inbox)
+        .add_label("Label_123")
+        .dry_run()
+        .unwrap();
+
+    assert_eq!(prepared.method, reqwest::Method::POST);
+    assert_eq!(
+        prepared.url,
+        "https://gmail.googleapis.com/gmail/v1/users/me/messages"
+    );
+    assert_eq!(
+        prepared.query.get("internalDateSource"),
+        Some(&"dateHeader".to_string())
+    );
+    let body: serde_json::Value = serde_json::from_str(&prepared.body.unwrap()).unwrap();
+    assert_eq!(body["raw"], "VG86IGFAZXhhbXBsZS5jb20");
+    assert_eq!(body["threadId"], "thread-1");
+    assert_eq!(body["labelIds"], serde_json::json!(["INBOX", "Label_123"]));
+}
+
+#[test]
+fn label_ids_are_repeated_in_the_query() {
+    let gc = dummy_google_client_valid();
+    let prepared = GmailClient::new(&gc)
+        .get_emails("me")
+        .label_ids(vec![
+            LabelId::from(SystemLabel::Unread),
+            LabelId::from("Label_123"),
+        ])
+        .dry_run()
+        .unwrap();
+
+    assert_eq!(
+        prepared.query.get_all("labelIds").collect::<Vec<_>>(),
+        ["UNREAD", "Label_123"]
+    );
+}
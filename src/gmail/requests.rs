@@ -1,27 +1,63 @@
-use anyhow::{anyhow, Error};
+use crate::error::Error;
+use base64::Engine;
 use reqwest::Method;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{auth::client::GoogleClient, utils::request::Request};
+use crate::{
+    auth::client::GoogleClient,
+    error::GoogleApiError,
+    utils::concurrency::{map_concurrent, DEFAULT_CONCURRENCY_LIMIT},
+    utils::request::{HeaderRequestTrait, PreparedRequest, RawParamRequestTrait, Request},
+    utils::url::encode_segment,
+};
 
-use super::types::{Message, MessageList};
+use super::types::{
+    BatchDeleteMessagesRequest, BatchModifyMessagesRequest, InternalDateSource, LabelId, Message,
+    MessageList, ModifyMessageRequest,
+};
 
 pub struct EmailListMode;
 pub struct EmailGetMode;
 pub struct EmailDeleteMode;
 pub struct TrashEmailMode;
+pub struct EmailBatchDeleteMode;
+pub struct EmailModifyMode;
+pub struct EmailBatchModifyMode;
+pub struct EmailInsertMode;
+pub struct EmailSendMode;
+pub struct EmailFullListMode;
+
+/// Messages whose base64url encoded form exceeds this size are sent through the upload
+/// endpoint as a multipart upload instead of inline in the JSON body.
+pub const MAX_INLINE_MESSAGE_SIZE: usize = 5 * 1024 * 1024;
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum LabelsRequest {
+    Modify(ModifyMessageRequest),
+    BatchModify(BatchModifyMessagesRequest),
+}
+
+/// The outcome of a batch request per message id, in the order of the ids. Each id carries the
+/// error Google returned for that message, e.g. a 404 for an unknown id.
+pub type BatchOutcome = Vec<(String, Result<(), GoogleApiError>)>;
 
 pub struct GmailClient<'a, T> {
     pub(super) request: Request<'a>,
     pub(super) message: Option<Message>,
+    pub(super) labels: Option<LabelsRequest>,
+    /// The message ids of the batch requests.
+    pub(super) ids: Vec<String>,
     pub(super) _mode: std::marker::PhantomData<T>,
 }
 
 impl<'a> GmailClient<'a, ()> {
-    pub fn new(client: &'a mut GoogleClient) -> Self {
+    pub fn new(client: &'a GoogleClient) -> Self {
         GmailClient {
             request: Request::new(client),
             message: None,
+            labels: None,
+            ids: Vec::new(),
             _mode: std::marker::PhantomData,
         }
     }
@@ -53,10 +89,14 @@ impl<'a> GmailClient<'a, ()> {
         let mut builder = GmailClient {
             request: self.request,
             message: None,
+            labels: None,
+            ids: Vec::new(),
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://gmail.googleapis.com/gmail/v1/users/{user_id}/messages");
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages",
+            encode_segment(user_id)
+        );
         builder.request.method = reqwest::Method::GET;
         builder
     }
@@ -86,10 +126,15 @@ impl<'a> GmailClient<'a, ()> {
         let mut builder = GmailClient {
             request: self.request,
             message: None,
+            labels: None,
+            ids: Vec::new(),
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://gmail.googleapis.com/gmail/v1/users/{user_id}/messages/{email_id}");
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/{}",
+            encode_segment(user_id),
+            encode_segment(email_id)
+        );
         builder.request.method = reqwest::Method::GET;
         builder
     }
@@ -120,10 +165,15 @@ impl<'a> GmailClient<'a, ()> {
         let mut builder = GmailClient {
             request: self.request,
             message: None,
+            labels: None,
+            ids: Vec::new(),
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://gmail.googleapis.com/gmail/v1/users/{user_id}/messages/{email_id}");
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/{}",
+            encode_segment(user_id),
+            encode_segment(email_id)
+        );
         builder.request.method = reqwest::Method::DELETE;
         builder
     }
@@ -152,10 +202,14 @@ impl<'a> GmailClient<'a, ()> {
         let mut builder = GmailClient {
             request: self.request,
             message: None,
+            labels: None,
+            ids: Vec::new(),
             _mode: std::marker::PhantomData,
         };
         builder.request.url = format!(
-            "https://gmail.googleapis.com/gmail/v1/users/{user_id}/messages/{email_id}/trash"
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/{}/trash",
+            encode_segment(user_id),
+            encode_segment(email_id)
         );
         builder.request.method = reqwest::Method::POST;
         builder
@@ -186,113 +240,421 @@ impl<'a> GmailClient<'a, ()> {
         let mut builder = GmailClient {
             request: self.request,
             message: None,
+            labels: None,
+            ids: Vec::new(),
+            _mode: std::marker::PhantomData,
+        };
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/{}/untrash",
+            encode_segment(user_id),
+            encode_segment(email_id)
+        );
+        builder.request.method = reqwest::Method::POST;
+        builder
+    }
+
+    /// Permanently delete multiple emails by user_id and a list of email ids in a single request.
+    /// Like delete_email this bypasses the trash, the messages can't be restored afterwards.
+    ///  
+    /// # Examples
+    ///  
+    /// `Axum is used in this example, but it can be adapted to other frameworks like Actix or
+    /// Rocket.`
+    ///  
+    /// ```rust
+    /// pub async fn delete_emails(State(state): State<AppState>, Json(ids): Json<Vec<String>>) {
+    ///
+    ///   let google_client_guard = state.google_client.lock().await;
+    ///   let client = google_client_guard.as_ref().unwrap();
+    ///    
+    ///   GmailClient::new(client)
+    ///   // "me" is a special value that refers to the authenticated user when used as user_id
+    ///   .batch_delete("me", ids)
+    ///   .request().await.unwrap();
+    /// }
+    ///```
+    pub fn batch_delete(
+        self,
+        user_id: &str,
+        ids: Vec<String>,
+    ) -> GmailClient<'a, EmailBatchDeleteMode> {
+        let mut builder = GmailClient {
+            request: self.request,
+            message: None,
+            labels: None,
+            ids: Vec::new(),
+            _mode: std::marker::PhantomData,
+        };
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/batchDelete",
+            encode_segment(user_id)
+        );
+        builder.request.method = reqwest::Method::POST;
+        let payload = BatchDeleteMessagesRequest { ids: ids.clone() };
+        // A list of ids always serializes
+        builder.request.body = serde_json::to_string(&payload).ok();
+        builder.ids = ids;
+        builder
+    }
+
+    /// Add or remove labels on a specific email by user_id and email_id.
+    ///  
+    /// # Examples
+    ///  
+    /// `Axum is used in this example, but it can be adapted to other frameworks like Actix or
+    /// Rocket.`
+    ///  
+    /// ```rust
+    /// pub async fn archive_email(State(state): State<AppState>, Path(email_id): Path<String>)
+    /// -> Json<Message> {
+    ///
+    ///   let google_client_guard = state.google_client.lock().await;
+    ///   let client = google_client_guard.as_ref().unwrap();
+    ///    
+    ///   let res = GmailClient::new(client)
+    ///   // "me" is a special value that refers to the authenticated user when used as user_id
+    ///   .modify_email("me", &email_id)
+    ///   .remove_label(SystemLabel::Inbox)
+    ///   .add_label("Label_123")
+    ///   .request().await.unwrap();
+    ///
+    ///   Json(res.unwrap())
+    /// }
+    ///```
+    pub fn modify_email(self, user_id: &str, email_id: &str) -> GmailClient<'a, EmailModifyMode> {
+        let mut builder = GmailClient {
+            request: self.request,
+            message: None,
+            labels: Some(LabelsRequest::Modify(ModifyMessageRequest::default())),
+            ids: Vec::new(),
+            _mode: std::marker::PhantomData,
+        };
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/{}/modify",
+            encode_segment(user_id),
+            encode_segment(email_id)
+        );
+        builder.request.method = reqwest::Method::POST;
+        builder
+    }
+
+    /// Add or remove labels on multiple emails by user_id and a list of email ids in a single
+    /// request.
+    ///  
+    /// # Examples
+    ///  
+    /// `Axum is used in this example, but it can be adapted to other frameworks like Actix or
+    /// Rocket.`
+    ///  
+    /// ```rust
+    /// pub async fn mark_as_read(State(state): State<AppState>, Json(ids): Json<Vec<String>>) {
+    ///
+    ///   let google_client_guard = state.google_client.lock().await;
+    ///   let client = google_client_guard.as_ref().unwrap();
+    ///    
+    ///   GmailClient::new(client)
+    ///   // "me" is a special value that refers to the authenticated user when used as user_id
+    ///   .batch_modify("me", ids)
+    ///   .remove_label(SystemLabel::Unread)
+    ///   .request().await.unwrap();
+    /// }
+    ///```
+    pub fn batch_modify(
+        self,
+        user_id: &str,
+        ids: Vec<String>,
+    ) -> GmailClient<'a, EmailBatchModifyMode> {
+        let mut builder = GmailClient {
+            request: self.request,
+            message: None,
+            labels: Some(LabelsRequest::BatchModify(BatchModifyMessagesRequest {
+                ids: ids.clone(),
+                ..Default::default()
+            })),
+            ids,
+            _mode: std::marker::PhantomData,
+        };
+        builder.request.url = format!(
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages/batchModify",
+            encode_segment(user_id)
+        );
+        builder.request.method = reqwest::Method::POST;
+        builder
+    }
+
+    /// Insert an email directly into the mailbox of the user, similar to IMAP APPEND.
+    /// The email isn't sent and bypasses most scanning and classification,
+    /// this is meant for importing existing emails (e.g. from an mbox archive).
+    ///  
+    /// # Examples
+    ///  
+    /// `Axum is used in this example, but it can be adapted to other frameworks like Actix or
+    /// Rocket.`
+    ///  
+    /// ```rust
+    /// pub async fn import_email(State(state): State<AppState>, raw: String) -> Json<Message> {
+    ///
+    ///   let google_client_guard = state.google_client.lock().await;
+    ///   let client = google_client_guard.as_ref().unwrap();
+    ///    
+    ///   let res = GmailClient::new(client)
+    ///   // "me" is a special value that refers to the authenticated user when used as user_id
+    ///   .insert_message("me")
+    ///   // The RFC 2822 formatted and base64url encoded email
+    ///   .set_raw(&raw)
+    ///   .internal_date_source(InternalDateSource::DateHeader)
+    ///   .add_label(SystemLabel::Inbox)
+    ///   .request().await.unwrap();
+    ///
+    ///   Json(res.unwrap())
+    /// }
+    ///```
+    pub fn insert_message(self, user_id: &str) -> GmailClient<'a, EmailInsertMode> {
+        self.message_upload_request(user_id, "")
+    }
+
+    /// Send an email to the recipients in its `To`, `Cc` and `Bcc` headers.
+    /// Messages larger than `MAX_INLINE_MESSAGE_SIZE` are uploaded through Gmail's upload
+    /// endpoint, so messages with large attachments can be sent as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let sent = GmailClient::new(client)
+    ///   .send_message("me")
+    ///   .set_mime("To: someone@example.com\r\nSubject: Report\r\n\r\nSee attachment")
+    ///   .request()
+    ///   .await?;
+    /// ```
+    pub fn send_message(self, user_id: &str) -> GmailClient<'a, EmailSendMode> {
+        self.message_upload_request(user_id, "/send")
+    }
+
+    fn message_upload_request<M>(self, user_id: &str, action: &str) -> GmailClient<'a, M> {
+        let mut builder = GmailClient {
+            request: self.request,
+            message: Some(Message::new()),
+            labels: None,
+            ids: Vec::new(),
             _mode: std::marker::PhantomData,
         };
         builder.request.url = format!(
-            "https://gmail.googleapis.com/gmail/v1/users/{user_id}/messages/{email_id}/untrash"
+            "https://gmail.googleapis.com/gmail/v1/users/{}/messages{action}",
+            encode_segment(user_id)
         );
         builder.request.method = reqwest::Method::POST;
         builder
     }
 }
 
+/// The client is left out, it holds the tokens.
+impl<T> std::fmt::Debug for GmailClient<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GmailClient")
+            .field("request", &self.request)
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
 impl<'a, T> GmailClient<'a, T> {
+    /// Renders the method, URL, query and body of the request for troubleshooting,
+    /// see `Request::debug_request`.
+    pub fn debug_request(&self) -> String {
+        self.request.debug_request(self.payload().as_deref())
+    }
+
+    /// Returns the request as it would be sent, without sending it. Messages over
+    /// `MAX_INLINE_MESSAGE_SIZE` are sent as multipart upload, which isn't reflected here.
+    pub fn dry_run(&self) -> Result<PreparedRequest, Error> {
+        self.request.prepare(self.body()?)
+    }
+
+    /// The JSON body the request is sent with, requests without a body ignore the message.
+    /// Without labels or a message the body set on the request is used, never `null`.
+    fn body(&self) -> Result<Option<String>, Error> {
+        if matches!(self.request.method, Method::GET | Method::DELETE) {
+            return Ok(None);
+        }
+        match (&self.labels, &self.message) {
+            (Some(labels), _) => Ok(Some(serde_json::to_string(labels)?)),
+            (None, Some(message)) => Ok(Some(serde_json::to_string(message)?)),
+            (None, None) => Ok(self.request.body.clone()),
+        }
+    }
+
+    /// Same as `body` for the debug output, which can't fail.
+    fn payload(&self) -> Option<String> {
+        self.body().ok().flatten()
+    }
     pub(super) async fn delete_request(&mut self) -> Result<(), Error> {
-        self.request.client.refresh_access_token_check().await?;
-        let res = self
-            .request
-            .client
-            .req_client
-            .delete(&self.request.url)
-            .query(&self.request.params)
-            .send()
-            .await?;
+        let res = self.request.send(None).await?;
 
         if res.status().is_success() {
             Ok(())
         } else {
-            Err(anyhow!("Failed to delete email: {}", res.status()))
+            Err(GoogleApiError::from_response(res).await.into())
         }
     }
 
     pub(super) async fn trash_request(&mut self) -> Result<(), Error> {
-        self.request.client.refresh_access_token_check().await?;
-        let res = self
-            .request
-            .client
-            .req_client
-            .post(&self.request.url)
-            .query(&self.request.params)
-            .send()
-            .await?;
+        let res = self.request.send(None).await?;
 
         if res.status().is_success() {
             Ok(())
         } else {
-            Err(anyhow!("Failed to trash email: {}", res.status()))
+            Err(GoogleApiError::from_response(res).await.into())
         }
     }
 
-    pub(super) async fn make_request<R>(&mut self) -> Result<Option<R>, Error>
+    pub(super) async fn batch_request(&mut self) -> Result<(), Error> {
+        let body = self.request.body.clone();
+        let res = self.request.send(body).await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(GoogleApiError::from_response(res).await.into())
+        }
+    }
+
+    /// The messages URL of the user a batch request is made for.
+    fn messages_url(&self) -> &str {
+        self.request
+            .url
+            .rsplit_once('/')
+            .map_or(self.request.url.as_str(), |(messages_url, _)| messages_url)
+    }
+
+    /// Sends one request per message id instead of a batch, with the query and headers of this
+    /// request, and reports the outcome of each. Gmail applies a batch as a whole, so this is
+    /// the only way to learn which ids failed. Errors before Google answered, like a failing
+    /// token refresh, fail the whole call.
+    async fn send_per_id<F>(&self, method: Method, per_id: F) -> Result<BatchOutcome, Error>
     where
-        R: DeserializeOwned,
+        F: Fn(&str) -> (String, Option<String>),
     {
-        self.request.client.refresh_access_token_check().await?;
-        match self.request.method {
-            Method::GET => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .get(&self.request.url)
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
+        // An invalid header fails once instead of for every id
+        self.request.prepare(None)?;
+        let client = self.request.client;
+        let outcomes = map_concurrent(&self.ids, DEFAULT_CONCURRENCY_LIMIT, |id| {
+            let (url, body) = per_id(id);
+            let mut request = Request::new(client);
+            request.method = method.clone();
+            request.url = url;
+            request.params = self.request.params.clone();
+            request.headers = self.request.headers.clone();
+            async move {
+                let res = request.send(body).await?;
+                let outcome = if res.status().is_success() {
+                    Ok(())
                 } else {
-                    Ok(None)
-                }
+                    Err(GoogleApiError::from_response(res).await)
+                };
+                Ok((id.clone(), outcome))
             }
+        })
+        .await;
+        outcomes.into_iter().collect()
+    }
 
-            Method::POST => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .post(&self.request.url)
-                    .body(serde_json::to_string(&self.message).unwrap())
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
+    /// Sends the message inline when it's small enough, otherwise as a multipart upload.
+    pub(super) async fn message_upload(&mut self) -> Result<Option<Message>, Error> {
+        let Some(body) = self.prepare_upload()? else {
+            return self.make_request().await;
+        };
+        let res = self.request.send_bytes(body).await?;
 
-            Method::PATCH => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .patch(&self.request.url)
-                    .body(serde_json::to_string(&self.message).unwrap())
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
-            _ => Err(anyhow!("Unsupported HTTP method")),
+        if res.status().is_success() {
+            Ok(Some(res.json().await?))
+        } else {
+            Err(GoogleApiError::from_response(res).await.into())
+        }
+    }
+
+    /// Switches the request to the upload endpoint when the message exceeds
+    /// `MAX_INLINE_MESSAGE_SIZE` and returns the multipart body, with the message metadata as
+    /// JSON part and the decoded MIME message as `message/rfc822` part.
+    pub(super) fn prepare_upload(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        let Some(message) = self.message.as_ref() else {
+            return Ok(None);
+        };
+        if message.raw.len() <= MAX_INLINE_MESSAGE_SIZE {
+            return Ok(None);
+        }
+
+        // The MIME message is kept as bytes, 8bit and binary parts aren't valid UTF-8
+        let mime = URL_SAFE_INDIFFERENT.decode(&message.raw).map_err(|_| {
+            Error::InvalidRequest("The raw message isn't base64url encoded".to_string())
+        })?;
+        let metadata = serde_json::to_string(&Message {
+            raw: String::new(),
+            ..message.clone()
+        })?;
+        let boundary = multipart_boundary(&mime);
+        let mut body = format!(
+            "--{boundary}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{metadata}\r\n\
+             --{boundary}\r\nContent-Type: message/rfc822\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(&mime);
+        body.extend_from_slice(format!("\r\n--{boundary}--").as_bytes());
+
+        self.request.url = self.request.url.replacen(
+            "https://gmail.googleapis.com/gmail/",
+            "https://gmail.googleapis.com/upload/gmail/",
+            1,
+        );
+        self.request
+            .params
+            .insert("uploadType".to_string(), "multipart".to_string());
+        self.request
+            .set_content_type(&format!("multipart/related; boundary={boundary}"));
+        Ok(Some(body))
+    }
+
+    pub(super) async fn make_request<R>(&mut self) -> Result<Option<R>, Error>
+    where
+        R: DeserializeOwned,
+    {
+        if !matches!(
+            self.request.method,
+            Method::GET | Method::POST | Method::PATCH
+        ) {
+            return Err(Error::InvalidRequest("Unsupported HTTP method".to_string()));
         }
+        self.request.body = self.body()?;
+        let body = self.request.body.clone();
+        let res = self.request.send(body).await?;
+
+        if res.status().is_success() {
+            Ok(Some(self.request.read_json(res).await?))
+        } else {
+            Err(GoogleApiError::from_response(res).await.into())
+        }
+    }
+}
+
+impl<'a, T> RawParamRequestTrait for GmailClient<'a, T> {
+    /// Sets an arbitrary query parameter, overriding any value set by the builder methods.
+    fn raw_param(mut self, key: &str, value: &str) -> Self {
+        self.request
+            .params
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+impl<'a, T> HeaderRequestTrait for GmailClient<'a, T> {
+    /// Sets a header for this request only, overriding a client-wide value of the header.
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.request.set_header(name, value);
+        self
+    }
+
+    fn conditional(mut self) -> Self {
+        self.request.conditional = true;
+        self
     }
 }
 
@@ -309,6 +671,38 @@ impl<'a> GmailClient<'a, EmailListMode> {
         self
     }
 
+    /// Retrieves the messages of all pages, stopping once `max_items` messages are retrieved.
+    /// The page size is reduced for the last page so no message is skipped, the returned
+    /// `next_page_token` resumes right after the last returned message and is empty when all
+    /// messages were retrieved.
+    pub async fn collect_up_to(mut self, max_items: Option<usize>) -> Result<MessageList, Error> {
+        let mut messages = MessageList {
+            next_page_token: self.request.page_token(),
+            ..MessageList::default()
+        };
+        while self
+            .request
+            .limit_page_size(max_items, messages.messages.len())
+        {
+            let Some(mut page) = self.make_request::<MessageList>().await? else {
+                return Err(Error::Request("Failed to list the emails".to_string()));
+            };
+            let mut items = std::mem::take(&mut messages.messages);
+            items.append(&mut page.messages);
+            messages = MessageList {
+                messages: items,
+                ..page
+            };
+            if messages.next_page_token.is_empty() {
+                break;
+            }
+            self.request
+                .params
+                .insert("pageToken".to_string(), messages.next_page_token.clone());
+        }
+        Ok(messages)
+    }
+
     /// Page token to retrieve a specific page of results in the list.
     pub fn page_token(mut self, token: i32) -> Self {
         self.request
@@ -325,6 +719,43 @@ impl<'a> GmailClient<'a, EmailListMode> {
         self
     }
 
+    /// Only return messages that have all of the given labels, accepts both system labels and
+    /// user label ids. This is faster than searching with `query("in:inbox")`.
+    pub fn label_ids(mut self, ids: Vec<impl Into<LabelId>>) -> Self {
+        for id in ids {
+            self.request
+                .params
+                .append("labelIds".to_string(), id.into().as_str().to_string());
+        }
+        self
+    }
+
+    /// Fetches every listed message after the list call, so the result contains full messages
+    /// instead of only their ids. At most `DEFAULT_CONCURRENCY_LIMIT` messages are fetched at
+    /// the same time, keep `max_results` small since every message costs a request.
+    /// One message that can't be fetched, e.g. because it was deleted after the list call,
+    /// fails the whole request, see `EmailFullListMode`'s `request`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let messages: Vec<Message> = GmailClient::new(client)
+    ///   .get_emails("me")
+    ///   .max_results(10)
+    ///   .with_full_messages()
+    ///   .request()
+    ///   .await?;
+    /// ```
+    pub fn with_full_messages(self) -> GmailClient<'a, EmailFullListMode> {
+        GmailClient {
+            request: self.request,
+            message: self.message,
+            labels: self.labels,
+            ids: Vec::new(),
+            _mode: std::marker::PhantomData,
+        }
+    }
+
     /// Only return messages matching the specified query.
     /// Supports the same query format as the Gmail search box.
     /// For example, "from:someuser@example.com rfc822msgid:<somemsgid@example.com> is:unread".
@@ -337,6 +768,38 @@ impl<'a> GmailClient<'a, EmailListMode> {
     }
 }
 
+impl<'a> GmailClient<'a, EmailFullListMode> {
+    /// Lists the messages and fetches each of them, the messages are returned in list order.
+    /// Fails when the list or any of the messages couldn't be retrieved, with the error of the
+    /// first failing message in list order. The other messages are still fetched but discarded,
+    /// list the ids and fetch them with `get_email` to handle failures per message.
+    pub async fn request(mut self) -> Result<Vec<Message>, Error> {
+        let Some(list) = self.make_request::<MessageList>().await? else {
+            return Err(Error::Request("Failed to list the emails".to_string()));
+        };
+
+        let client = self.request.client;
+        let messages_url = &self.request.url;
+        let results = map_concurrent(
+            list.messages,
+            DEFAULT_CONCURRENCY_LIMIT,
+            |message| async move {
+                let url =
+                    client.resolve_url(&format!("{messages_url}/{}", encode_segment(&message.id)));
+                let no_query: &[(&str, &str)] = &[];
+                client
+                    .get_json::<Message, _>(&url, no_query)
+                    .await?
+                    .ok_or_else(|| {
+                        Error::Request(format!("Email {} has an empty response", message.id))
+                    })
+            },
+        )
+        .await;
+        results.into_iter().collect()
+    }
+}
+
 impl<'a> GmailClient<'a, EmailGetMode> {
     pub async fn request(mut self) -> Result<Option<Message>, Error> {
         self.make_request().await
@@ -354,3 +817,192 @@ impl<'a> GmailClient<'a, TrashEmailMode> {
         self.trash_request().await
     }
 }
+
+impl<'a> GmailClient<'a, EmailBatchDeleteMode> {
+    pub async fn request(mut self) -> Result<(), Error> {
+        self.batch_request().await
+    }
+
+    /// Same as `request`, but deletes the messages one by one and returns the outcome for every
+    /// id, see `BatchOutcome`. This costs one request per id.
+    pub async fn request_per_id(self) -> Result<BatchOutcome, Error> {
+        let messages_url = self.messages_url();
+        self.send_per_id(Method::DELETE, |id| {
+            (format!("{messages_url}/{}", encode_segment(id)), None)
+        })
+        .await
+    }
+}
+
+impl<'a> GmailClient<'a, EmailModifyMode> {
+    pub async fn request(mut self) -> Result<Option<Message>, Error> {
+        self.make_request().await
+    }
+
+    /// Add a label to the email, accepts both system labels and user label ids.
+    pub fn add_label(self, label: impl Into<LabelId>) -> Self {
+        let label = label.into().as_str().to_string();
+        self.modify_labels(|labels| labels.add_label_ids.push(label))
+    }
+
+    /// Remove a label from the email, accepts both system labels and user label ids.
+    pub fn remove_label(self, label: impl Into<LabelId>) -> Self {
+        let label = label.into().as_str().to_string();
+        self.modify_labels(|labels| labels.remove_label_ids.push(label))
+    }
+
+    fn modify_labels<F>(mut self, modifier: F) -> Self
+    where
+        F: FnOnce(&mut ModifyMessageRequest),
+    {
+        if let Some(LabelsRequest::Modify(ref mut labels)) = self.labels {
+            modifier(labels);
+        }
+        self
+    }
+}
+
+impl<'a> GmailClient<'a, EmailBatchModifyMode> {
+    pub async fn request(mut self) -> Result<(), Error> {
+        self.request.body = self.body()?;
+        self.batch_request().await
+    }
+
+    /// Same as `request`, but modifies the messages one by one and returns the outcome for every
+    /// id, see `BatchOutcome`. This costs one request per id.
+    pub async fn request_per_id(self) -> Result<BatchOutcome, Error> {
+        let labels = match &self.labels {
+            Some(LabelsRequest::BatchModify(labels)) => ModifyMessageRequest {
+                add_label_ids: labels.add_label_ids.clone(),
+                remove_label_ids: labels.remove_label_ids.clone(),
+            },
+            _ => ModifyMessageRequest::default(),
+        };
+        let body = serde_json::to_string(&labels)?;
+        let messages_url = self.messages_url();
+        self.send_per_id(Method::POST, |id| {
+            let url = format!("{messages_url}/{}/modify", encode_segment(id));
+            (url, Some(body.clone()))
+        })
+        .await
+    }
+
+    /// Add a label to the emails, accepts both system labels and user label ids.
+    pub fn add_label(self, label: impl Into<LabelId>) -> Self {
+        let label = label.into().as_str().to_string();
+        self.modify_labels(|labels| labels.add_label_ids.push(label))
+    }
+
+    /// Remove a label from the emails, accepts both system labels and user label ids.
+    pub fn remove_label(self, label: impl Into<LabelId>) -> Self {
+        let label = label.into().as_str().to_string();
+        self.modify_labels(|labels| labels.remove_label_ids.push(label))
+    }
+
+    fn modify_labels<F>(mut self, modifier: F) -> Self
+    where
+        F: FnOnce(&mut BatchModifyMessagesRequest),
+    {
+        if let Some(LabelsRequest::BatchModify(ref mut labels)) = self.labels {
+            modifier(labels);
+        }
+        self
+    }
+}
+
+impl<'a> GmailClient<'a, EmailInsertMode> {
+    pub async fn request(mut self) -> Result<Option<Message>, Error> {
+        self.message_upload().await
+    }
+
+    /// The entire email in an RFC 2822 formatted and base64url encoded string.
+    pub fn set_raw(self, raw: &str) -> Self {
+        self.modify_message(|message| message.raw = raw.to_string())
+    }
+
+    /// The entire email in RFC 2822 format, it's base64url encoded before sending.
+    pub fn set_mime(self, mime: &str) -> Self {
+        self.set_raw(&base64::engine::general_purpose::URL_SAFE.encode(mime))
+    }
+
+    /// Source for Gmail's internal date of the message, defaults to receivedTime.
+    pub fn internal_date_source(mut self, source: InternalDateSource) -> Self {
+        self.request.params.insert(
+            "internalDateSource".to_string(),
+            source.as_str().to_string(),
+        );
+        self
+    }
+
+    /// Inserts the email into an existing thread.
+    pub fn set_thread_id(self, thread_id: &str) -> Self {
+        self.modify_message(|message| message.thread_id = thread_id.to_string())
+    }
+
+    /// Add a label to the inserted email, accepts both system labels and user label ids.
+    pub fn add_label(self, label: impl Into<LabelId>) -> Self {
+        let label = label.into().as_str().to_string();
+        self.modify_message(|message| message.label_ids.push(label))
+    }
+
+    fn modify_message<F>(mut self, modifier: F) -> Self
+    where
+        F: FnOnce(&mut Message),
+    {
+        if let Some(ref mut message) = self.message {
+            modifier(message);
+        }
+        self
+    }
+}
+
+/// Decodes base64url with or without padding, Gmail accepts both.
+const URL_SAFE_INDIFFERENT: base64::engine::GeneralPurpose = base64::engine::GeneralPurpose::new(
+    &base64::alphabet::URL_SAFE,
+    base64::engine::GeneralPurposeConfig::new()
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+);
+
+/// Returns a multipart boundary that doesn't occur in the message.
+fn multipart_boundary(mime: &[u8]) -> String {
+    let mut boundary = "google_workspace_apis_boundary".to_string();
+    while mime
+        .windows(boundary.len())
+        .any(|window| window == boundary.as_bytes())
+    {
+        boundary.push('_');
+    }
+    boundary
+}
+
+impl<'a> GmailClient<'a, EmailSendMode> {
+    pub async fn request(mut self) -> Result<Option<Message>, Error> {
+        self.message_upload().await
+    }
+
+    /// The entire email in an RFC 2822 formatted and base64url encoded string.
+    pub fn set_raw(self, raw: &str) -> Self {
+        self.modify_message(|message| message.raw = raw.to_string())
+    }
+
+    /// The entire email in RFC 2822 format, it's base64url encoded before sending.
+    pub fn set_mime(self, mime: &str) -> Self {
+        self.set_raw(&base64::engine::general_purpose::URL_SAFE.encode(mime))
+    }
+
+    /// Sends the email as reply in an existing thread. The `References` and `In-Reply-To`
+    /// headers of the email have to match the thread as well.
+    pub fn set_thread_id(self, thread_id: &str) -> Self {
+        self.modify_message(|message| message.thread_id = thread_id.to_string())
+    }
+
+    fn modify_message<F>(mut self, modifier: F) -> Self
+    where
+        F: FnOnce(&mut Message),
+    {
+        if let Some(ref mut message) = self.message {
+            modifier(message);
+        }
+        self
+    }
+}
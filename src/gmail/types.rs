@@ -93,6 +93,14 @@ pub struct Message {
         deserialize_with = "crate::utils::deserialize::deserialize_nullable_string::deserialize"
     )]
     pub raw: String,
+
+    /**
+     * Fields returned by Google that aren't modeled by this struct.
+     * Requires the `capture-extra` feature.
+     */
+    #[cfg(feature = "capture-extra")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -213,7 +221,7 @@ pub struct MessagePartBody {
     pub attachment_id: String,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct MessageList {
     /**
      * List of messages.
@@ -237,7 +245,8 @@ pub struct MessageList {
     pub next_page_token: String,
 
     /**
-     * Estimated total number of results.
+     * Estimated total number of results. This is only an estimate,
+     * use `has_more` to know whether another page exists.
      */
     #[serde(
         default,
@@ -248,6 +257,13 @@ pub struct MessageList {
     pub result_size_estimate: i64,
 }
 
+impl MessageList {
+    /// Returns true when there is another page of results, retrieve it with `page_token`.
+    pub fn has_more(&self) -> bool {
+        !self.next_page_token.is_empty()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ModifyMessageRequest {
     /**
@@ -267,6 +283,64 @@ pub struct ModifyMessageRequest {
     pub remove_label_ids: Vec<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct BatchModifyMessagesRequest {
+    /**
+     * The IDs of the messages to modify.
+     */
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub ids: Vec<String>,
+
+    /**
+     * Label IDs to add to the messages.
+     */
+    #[serde(skip_serializing_if = "Vec::is_empty", default, rename = "addLabelIds")]
+    pub add_label_ids: Vec<String>,
+
+    /**
+     * Label IDs to remove from the messages.
+     */
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        rename = "removeLabelIds"
+    )]
+    pub remove_label_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct BatchDeleteMessagesRequest {
+    /**
+     * The IDs of the messages to delete.
+     */
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub ids: Vec<String>,
+}
+
+impl Message {
+    pub fn new() -> Self {
+        Message {
+            id: String::new(),
+            thread_id: String::new(),
+            label_ids: Vec::new(),
+            snippet: String::new(),
+            history_id: String::new(),
+            internal_date: String::new(),
+            payload: None,
+            size_estimate: 0,
+            raw: String::new(),
+            #[cfg(feature = "capture-extra")]
+            extra: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl Default for Message {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MessagePartBody {
     pub fn new() -> Self {
         MessagePartBody {
@@ -302,5 +376,124 @@ impl Default for MessagePart {
     }
 }
 
+/// Labels that are created and managed by Gmail itself.
+/// See [Managing labels](https://developers.google.com/workspace/gmail/api/guides/labels)
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum SystemLabel {
+    Inbox,
+    Spam,
+    Trash,
+    Unread,
+    Starred,
+    Important,
+    Sent,
+    Draft,
+    Chat,
+    CategoryPersonal,
+    CategorySocial,
+    CategoryPromotions,
+    CategoryUpdates,
+    CategoryForums,
+}
+
+impl SystemLabel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SystemLabel::Inbox => "INBOX",
+            SystemLabel::Spam => "SPAM",
+            SystemLabel::Trash => "TRASH",
+            SystemLabel::Unread => "UNREAD",
+            SystemLabel::Starred => "STARRED",
+            SystemLabel::Important => "IMPORTANT",
+            SystemLabel::Sent => "SENT",
+            SystemLabel::Draft => "DRAFT",
+            SystemLabel::Chat => "CHAT",
+            SystemLabel::CategoryPersonal => "CATEGORY_PERSONAL",
+            SystemLabel::CategorySocial => "CATEGORY_SOCIAL",
+            SystemLabel::CategoryPromotions => "CATEGORY_PROMOTIONS",
+            SystemLabel::CategoryUpdates => "CATEGORY_UPDATES",
+            SystemLabel::CategoryForums => "CATEGORY_FORUMS",
+        }
+    }
+
+    /// Returns the matching system label, or None if the id belongs to a user label.
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "INBOX" => Some(SystemLabel::Inbox),
+            "SPAM" => Some(SystemLabel::Spam),
+            "TRASH" => Some(SystemLabel::Trash),
+            "UNREAD" => Some(SystemLabel::Unread),
+            "STARRED" => Some(SystemLabel::Starred),
+            "IMPORTANT" => Some(SystemLabel::Important),
+            "SENT" => Some(SystemLabel::Sent),
+            "DRAFT" => Some(SystemLabel::Draft),
+            "CHAT" => Some(SystemLabel::Chat),
+            "CATEGORY_PERSONAL" => Some(SystemLabel::CategoryPersonal),
+            "CATEGORY_SOCIAL" => Some(SystemLabel::CategorySocial),
+            "CATEGORY_PROMOTIONS" => Some(SystemLabel::CategoryPromotions),
+            "CATEGORY_UPDATES" => Some(SystemLabel::CategoryUpdates),
+            "CATEGORY_FORUMS" => Some(SystemLabel::CategoryForums),
+            _ => None,
+        }
+    }
+}
+
+/// The id of a label, either one of the Gmail system labels or a label created by the user.
+/// User label ids are opaque strings like "Label_123".
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum LabelId {
+    System(SystemLabel),
+    User(String),
+}
+
+impl LabelId {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LabelId::System(label) => label.as_str(),
+            LabelId::User(id) => id,
+        }
+    }
+}
+
+impl From<SystemLabel> for LabelId {
+    fn from(label: SystemLabel) -> Self {
+        LabelId::System(label)
+    }
+}
+
+impl From<&str> for LabelId {
+    fn from(id: &str) -> Self {
+        match SystemLabel::from_id(id) {
+            Some(label) => LabelId::System(label),
+            None => LabelId::User(id.to_string()),
+        }
+    }
+}
+
+impl From<String> for LabelId {
+    fn from(id: String) -> Self {
+        LabelId::from(id.as_str())
+    }
+}
+
+/// Source for Gmail's internal date of an inserted message.
+#[non_exhaustive]
+pub enum InternalDateSource {
+    /// Internal message date set to current time when received by Gmail.
+    ReceivedTime,
+    /// Internal message time based on 'Date' header in email, when valid.
+    DateHeader,
+}
+
+impl InternalDateSource {
+    pub fn as_str(&self) -> &str {
+        match self {
+            InternalDateSource::ReceivedTime => "receivedTime",
+            InternalDateSource::DateHeader => "dateHeader",
+        }
+    }
+}
+
 pub enum GetMessageFormat {}
 //TODO: finish format enum https://developers.google.com/workspace/gmail/api/reference/rest/v1/Format
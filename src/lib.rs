@@ -8,6 +8,10 @@
 //!   config fields like client_id, client_secret, and redirect_uri to your `Config` struct.
 //! - Run examples with `cargo run --example axum_calendar_example --features calendar`
 //!
+//! Enums that mirror values defined by Google, like `Scope`, `EventType` and the error types,
+//! are `#[non_exhaustive]` so new values can be added without a breaking release.
+//! Matches on them need a wildcard arm.
+//!
 
 /// Module for the Google Calendar API interactions.
 /// This requires the `calendar` feature to be enabled.
@@ -24,5 +28,18 @@ pub mod auth;
 #[cfg(feature = "gmail")]
 pub mod gmail;
 
+/// Module for the errors returned by the Google APIs
+pub mod error;
+
+/// Module for exporting the JSON schemas of the response types.
+/// This requires the `schema` feature to be enabled.
+#[cfg(feature = "schema")]
+pub mod schemas;
+#[cfg(all(test, feature = "schema"))]
+mod schemas_tests;
+
 /// Helper module for utility functions
 pub mod utils;
+
+#[cfg(test)]
+mod send_tests;
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use schemars::{schema_for, Schema};
+
+/// Exports the JSON schemas of the public response types, keyed by type name.
+/// Only types of the enabled API features are included.
+///
+/// # Examples
+///
+/// ```rust
+/// let schemas = google_workspace_apis::schemas::export();
+/// for (name, schema) in schemas {
+///     std::fs::write(
+///         format!("schemas/{name}.json"),
+///         serde_json::to_string_pretty(&schema).unwrap(),
+///     )
+///     .unwrap();
+/// }
+/// ```
+pub fn export() -> HashMap<&'static str, Schema> {
+    #[allow(unused_mut)]
+    let mut schemas = HashMap::from([
+        ("AccessToken", schema_for!(crate::auth::client::AccessToken)),
+        (
+            "DeviceCodeResponse",
+            schema_for!(crate::auth::device_code::DeviceCodeResponse),
+        ),
+    ]);
+
+    #[cfg(feature = "calendar")]
+    {
+        use crate::calendar::events::types::{Channel, Event, EventList};
+        schemas.insert("Event", schema_for!(Event));
+        schemas.insert("EventList", schema_for!(EventList));
+        schemas.insert("Channel", schema_for!(Channel));
+    }
+
+    #[cfg(feature = "tasks")]
+    {
+        use crate::tasks::tasklist::types::{TaskList, TaskLists};
+        use crate::tasks::types::{Task, Tasks};
+        schemas.insert("Task", schema_for!(Task));
+        schemas.insert("Tasks", schema_for!(Tasks));
+        schemas.insert("TaskList", schema_for!(TaskList));
+        schemas.insert("TaskLists", schema_for!(TaskLists));
+    }
+
+    #[cfg(feature = "gmail")]
+    {
+        use crate::gmail::types::{Message, MessageList};
+        schemas.insert("Message", schema_for!(Message));
+        schemas.insert("MessageList", schema_for!(MessageList));
+    }
+
+    schemas
+}
@@ -0,0 +1,26 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::schemas::export;
+
+#[test]
+fn export_contains_the_response_types_of_the_enabled_features() {
+    #[allow(unused_mut)]
+    let mut expected = vec!["AccessToken", "DeviceCodeResponse"];
+    #[cfg(feature = "calendar")]
+    expected.extend(["Event", "EventList", "Channel"]);
+    #[cfg(feature = "tasks")]
+    expected.extend(["Task", "Tasks", "TaskList", "TaskLists"]);
+    #[cfg(feature = "gmail")]
+    expected.extend(["Message", "MessageList"]);
+
+    let schemas = export();
+    assert_eq!(schemas.len(), expected.len());
+    for name in expected {
+        let schema = schemas.get(name).unwrap();
+        let json = serde_json::to_value(schema).unwrap();
+        assert!(
+            json.is_object(),
+            "{name} doesn't serialize to a schema object"
+        );
+    }
+}
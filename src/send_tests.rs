@@ -0,0 +1,43 @@
+//! Compile-time checks that the client, the request builders and their futures are `Send`,
+//! so requests can be made from spawned tokio tasks and axum handlers.
+#![allow(dead_code)]
+
+use crate::auth::client::GoogleClient;
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+fn assert_send_val<T: Send>(_: &T) {}
+
+#[test]
+fn google_client_is_send_and_sync() {
+    assert_send::<GoogleClient>();
+    assert_sync::<GoogleClient>();
+    assert_send::<crate::error::Error>();
+    assert_sync::<crate::error::Error>();
+}
+
+#[cfg(feature = "calendar")]
+fn calendar_futures_are_send(client: &GoogleClient) {
+    use crate::calendar::events::requests::{CalendarEventsClient, EventListMode};
+    assert_send::<CalendarEventsClient<'static, EventListMode>>();
+
+    let mut builder = CalendarEventsClient::new(client).get_events("primary");
+    assert_send_val(&builder.request());
+}
+
+#[cfg(feature = "tasks")]
+fn tasks_futures_are_send(client: &GoogleClient) {
+    use crate::tasks::requests::{TaskListMode, TasksClient};
+    assert_send::<TasksClient<'static, TaskListMode>>();
+
+    let mut builder = TasksClient::new(client).get_all_tasks();
+    assert_send_val(&builder.request());
+}
+
+#[cfg(feature = "gmail")]
+fn gmail_futures_are_send(client: &GoogleClient) {
+    use crate::gmail::requests::{EmailListMode, GmailClient};
+    assert_send::<GmailClient<'static, EmailListMode>>();
+
+    assert_send_val(&GmailClient::new(client).get_emails("me").request());
+}
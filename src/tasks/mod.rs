@@ -1,3 +1,13 @@
+#[cfg(test)]
+mod request_tests;
 pub mod requests;
 pub mod tasklist;
+pub mod prelude {
+    pub use crate::tasks::requests::TasksClient;
+    pub use crate::tasks::tasklist::types::{TaskList, TaskLists};
+    pub use crate::tasks::types::{Task, TaskLink, TaskLinkType, Tasks};
+    pub use crate::utils::request::HeaderRequestTrait;
+    pub use crate::utils::request::PaginationRequestTrait;
+    pub use crate::utils::request::RawParamRequestTrait;
+}
 pub mod types;
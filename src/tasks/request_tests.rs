@@ -0,0 +1,572 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::auth::client::GoogleClient;
+use crate::utils::request::PaginationRequestTrait;
+
+use super::{
+    requests::{incomplete_subtasks, TasksClient, MAX_NOTES_LENGTH, MAX_TITLE_LENGTH},
+    tasklist::types::TaskLists,
+    types::{ContextType, Task, TaskLink, TaskLinkType, TaskNode, Tasks},
+};
+
+fn dummy_google_client_valid() -> GoogleClient {
+    GoogleClient::mock()
+}
+
+#[tokio::test]
+async fn insert_task_rejects_too_long_title() {
+    let gc = dummy_google_client_valid();
+    let res = TasksClient::new(&gc)
+        .insert_task("list")
+        .set_task_title(&"a".repeat(MAX_TITLE_LENGTH + 1))
+        .request()
+        .await;
+    assert!(res.unwrap_err().to_string().starts_with("Task title"));
+}
+
+#[tokio::test]
+async fn insert_task_rejects_too_long_notes() {
+    let gc = dummy_google_client_valid();
+    let res = TasksClient::new(&gc)
+        .insert_task("list")
+        .set_task_notes(&"a".repeat(MAX_NOTES_LENGTH + 1))
+        .request()
+        .await;
+    assert!(res.unwrap_err().to_string().starts_with("Task notes"));
+}
+
+#[test]
+fn task_time_filters_are_serialized_as_rfc3339() {
+    let gc = dummy_google_client_valid();
+    let due_min = chrono::DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let client = TasksClient::new(&gc)
+        .get_tasks("list")
+        .get_due_min(due_min)
+        .get_updated_min(due_min);
+
+    let p = &client.request.params;
+    assert_eq!(
+        p.get("dueMin").map(String::as_str),
+        Some("2025-01-01T00:00:00+00:00")
+    );
+    assert_eq!(
+        p.get("updatedMin").map(String::as_str),
+        Some("2025-01-01T00:00:00+00:00")
+    );
+}
+
+#[test]
+fn completed_filters_enable_show_completed() {
+    let gc = dummy_google_client_valid();
+    let client = TasksClient::new(&gc)
+        .get_tasks("list")
+        .get_completed_min(chrono::Utc::now());
+
+    assert_eq!(
+        client
+            .request
+            .params
+            .get("showCompleted")
+            .map(String::as_str),
+        Some("true")
+    );
+}
+
+#[test]
+fn task_lists_deserialize_ids_and_page_token() {
+    let lists: TaskLists = serde_json::from_str(
+        r#"{
+            "kind": "tasks#taskLists",
+            "nextPageToken": "next",
+            "items": [{
+                "id": "list1",
+                "title": "My Tasks",
+                "etag": "\"etag\"",
+                "updated": "2025-01-01T00:00:00.000Z",
+                "selfLink": "https://www.googleapis.com/tasks/v1/users/@me/lists/list1"
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(lists.next_page_token, "next");
+    let list = &lists.items[0];
+    assert_eq!(list.id, "list1");
+    assert_eq!(list.title, "My Tasks");
+    assert!(list.updated.is_some());
+    assert!(list.self_link.ends_with("/lists/list1"));
+}
+
+#[test]
+fn task_ids_are_percent_encoded_in_urls() {
+    let gc = dummy_google_client_valid();
+    let client = TasksClient::new(&gc).delete_task("task/1", "list?x");
+    assert_eq!(
+        client.request.url,
+        "https://tasks.googleapis.com/tasks/v1/lists/list%3Fx/tasks/task%2F1"
+    );
+}
+
+#[test]
+fn unknown_context_types_deserialize_as_unknown() {
+    let known: ContextType = serde_json::from_str(r#""GMAIL""#).unwrap();
+    assert_eq!(known, ContextType::Gmail);
+    let unknown: ContextType = serde_json::from_str(r#""CALENDAR""#).unwrap();
+    assert_eq!(unknown, ContextType::Unknown);
+}
+
+#[test]
+fn complete_task_sets_if_match() {
+    let gc = dummy_google_client_valid();
+    let client = TasksClient::new(&gc)
+        .complete_task("task1", "list1")
+        .set_if_match("\"etag-1\"");
+    assert_eq!(client.request.headers["if-match"], "\"etag-1\"");
+}
+
+#[test]
+fn dry_run_returns_the_composed_request() {
+    let mut gc = dummy_google_client_valid();
+    gc.set_request_reason("sync");
+    let prepared = TasksClient::new(&gc)
+        .complete_task("task1", "list1")
+        .set_if_match("\"etag-1\"")
+        .dry_run()
+        .unwrap();
+
+    assert_eq!(prepared.method, reqwest::Method::PATCH);
+    assert_eq!(
+        prepared.url,
+        "https://tasks.googleapis.com/tasks/v1/lists/list1/tasks/task1"
+    );
+    assert_eq!(prepared.body.as_deref(), Some(r#"{"status":"completed"}"#));
+    assert_eq!(prepared.headers["if-match"], "\"etag-1\"");
+    assert_eq!(prepared.headers["x-goog-request-reason"], "sync");
+    assert_eq!(prepared.headers["content-type"], "application/json");
+    assert!(prepared.headers.get("authorization").is_none());
+}
+
+#[test]
+fn incomplete_subtasks_include_every_level_but_skip_completed() {
+    let task = |id: &str, parent: &str, status: &str| Task {
+        id: id.to_string(),
+        parent: parent.to_string(),
+        status: status.to_string(),
+        ..Default::default()
+    };
+    let tasks = vec![
+        task("root", "", "needsAction"),
+        task("a", "root", "needsAction"),
+        task("b", "root", "completed"),
+        task("a1", "a", "needsAction"),
+        task("b1", "b", "needsAction"),
+        task("other", "", "needsAction"),
+    ];
+
+    let mut subtasks = incomplete_subtasks(&tasks, "root");
+    subtasks.sort();
+    assert_eq!(subtasks, ["a", "a1", "b1"]);
+    assert!(incomplete_subtasks(&tasks, "other").is_empty());
+}
+
+#[test]
+fn tasks_into_tree_nests_subtasks_by_position() {
+    let task = |id: &str, parent: &str, position: &str| Task {
+        id: id.to_string(),
+        parent: parent.to_string(),
+        position: position.to_string(),
+        ..Default::default()
+    };
+    let tasks = Tasks {
+        items: vec![
+            task("b", "", "00000000000000000002"),
+            task("a2", "a", "00000000000000000002"),
+            task("a", "", "00000000000000000001"),
+            task("a1", "a", "00000000000000000001"),
+            task("orphan", "missing", "00000000000000000003"),
+        ],
+        ..Default::default()
+    };
+
+    let tree = tasks.into_tree();
+    let ids = |nodes: &[TaskNode]| {
+        nodes
+            .iter()
+            .map(|node| node.task.id.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&tree), ["a", "b", "orphan"]);
+    assert_eq!(ids(&tree[0].children), ["a1", "a2"]);
+    assert!(tree[1].children.is_empty());
+}
+
+#[test]
+fn task_link_type_round_trips() {
+    let link = TaskLink::new(
+        TaskLinkType::ChatMessage,
+        "https://chat.google.com/room/x",
+        "Discussion",
+    );
+    assert_eq!(link.r#type, "chat_message");
+    assert_eq!(link.link_type(), Some(TaskLinkType::ChatMessage));
+
+    let unknown: TaskLink = serde_json::from_str(r#"{"type": "calendar_event"}"#).unwrap();
+    assert_eq!(unknown.link_type(), None);
+}
+
+#[test]
+fn assignment_origin_accessors_walk_the_assignment_info() {
+    let task: Task = serde_json::from_value(serde_json::json!({
+        "id": "t1",
+        "assignmentInfo": {
+            "linkToTask": "https://docs.google.com/document/d/doc1/edit?disco=c1",
+            "surfaceType": "DOCUMENT",
+            "driveResourceInfo": { "driveFileId": "doc1" }
+        }
+    }))
+    .unwrap();
+    assert_eq!(task.origin_surface(), Some(ContextType::Document));
+    assert_eq!(task.drive_file_id(), Some("doc1"));
+    assert_eq!(
+        task.origin_link(),
+        Some("https://docs.google.com/document/d/doc1/edit?disco=c1")
+    );
+    assert_eq!(task.chat_space(), None);
+
+    assert_eq!(Task::new().origin_surface(), None);
+    assert_eq!(Task::new().drive_file_id(), None);
+}
+
+#[test]
+fn due_in_timezone_keeps_the_local_date() {
+    let gc = dummy_google_client_valid();
+    // 23:30 UTC is already the next morning in UTC+10
+    let sydney = chrono::FixedOffset::east_opt(10 * 3600).unwrap();
+    let late_evening_utc = chrono::DateTime::parse_from_rfc3339("2026-02-01T23:30:00Z")
+        .unwrap()
+        .to_utc();
+
+    let builder = TasksClient::new(&gc)
+        .insert_task("list")
+        .set_task_due_in_timezone(late_evening_utc.with_timezone(&sydney));
+    let body: serde_json::Value =
+        serde_json::from_str(&builder.dry_run().unwrap().body.unwrap()).unwrap();
+    assert!(body["due"]
+        .as_str()
+        .unwrap()
+        .starts_with("2026-02-02T00:00:00"));
+    let task: Task = serde_json::from_value(body).unwrap();
+    assert_eq!(task.due_date(), chrono::NaiveDate::from_ymd_opt(2026, 2, 2));
+
+    // The same instant in UTC is still the day before
+    let builder = TasksClient::new(&gc)
+        .insert_task("list")
+        .set_task_due_in_timezone(late_evening_utc);
+    let task: Task = serde_json::from_str(&builder.dry_run().unwrap().body.unwrap()).unwrap();
+    assert_eq!(task.due_date(), chrono::NaiveDate::from_ymd_opt(2026, 2, 1));
+}
+
+#[test]
+fn patched_due_in_timezone_keeps_the_other_fields() {
+    let gc = dummy_google_client_valid();
+    let sydney = chrono::FixedOffset::east_opt(10 * 3600).unwrap();
+    let late_evening_utc = chrono::DateTime::parse_from_rfc3339("2026-02-01T23:30:00Z").unwrap();
+
+    let prepared = TasksClient::new(&gc)
+        .complete_task("task1", "list1")
+        .set_task_due_in_timezone(late_evening_utc.with_timezone(&sydney))
+        .dry_run()
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_str(&prepared.body.unwrap()).unwrap();
+    assert_eq!(body["status"], "completed");
+    let task: Task = serde_json::from_value(body).unwrap();
+    assert_eq!(task.due_date(), chrono::NaiveDate::from_ymd_opt(2026, 2, 2));
+}
+
+#[tokio::test]
+async fn unsuccessful_response_is_an_api_error() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/tasks")
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Not Found"}}"#)
+        .create_async()
+        .await;
+
+    let gc = dummy_google_client_valid();
+    let mut builder = TasksClient::new(&gc).get_tasks("missing");
+    builder.request.url = format!("{}/tasks", server.url());
+    let err = builder.request().await.unwrap_err();
+
+    let api_error = err.api_error().unwrap();
+    assert_eq!(api_error.status(), reqwest::StatusCode::NOT_FOUND);
+    mock.assert_async().await;
+}
+
+fn mock_task_list_client(server: &mockito::Server) -> GoogleClient {
+    let mut gc = dummy_google_client_valid();
+    gc.set_base_url(&server.url());
+    gc
+}
+
+async fn mock_complete(server: &mut mockito::Server, task_id: &str) -> mockito::Mock {
+    server
+        .mock(
+            "PATCH",
+            format!("/tasks/v1/lists/list/tasks/{task_id}").as_str(),
+        )
+        .match_body(mockito::Matcher::Json(
+            serde_json::json!({"status": "completed"}),
+        ))
+        .with_status(200)
+        .with_body(format!(r#"{{"id": "{task_id}", "status": "completed"}}"#))
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn complete_task_recursive_completes_nested_subtasks() {
+    let mut server = mockito::Server::new_async().await;
+    let list = server
+        .mock("GET", "/tasks/v1/lists/list/tasks")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(
+            serde_json::json!({"items": [
+                {"id": "root", "status": "needsAction"},
+                {"id": "child", "parent": "root", "status": "needsAction"},
+                {"id": "done", "parent": "root", "status": "completed"},
+                {"id": "grandchild", "parent": "child", "status": "needsAction"},
+                {"id": "other", "status": "needsAction"},
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let completed = [
+        mock_complete(&mut server, "root").await,
+        mock_complete(&mut server, "child").await,
+        mock_complete(&mut server, "grandchild").await,
+    ];
+
+    let gc = mock_task_list_client(&server);
+    let count = TasksClient::new(&gc)
+        .complete_task_recursive("root", "list")
+        .await
+        .unwrap();
+
+    assert_eq!(count, 3);
+    list.assert_async().await;
+    for mock in completed {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn complete_task_recursive_skips_a_completed_parent() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/tasks/v1/lists/list/tasks")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(
+            serde_json::json!({"items": [
+                {"id": "root", "status": "completed"},
+                {"id": "child", "parent": "root", "status": "needsAction"},
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let child = mock_complete(&mut server, "child").await;
+    let root = server
+        .mock("PATCH", "/tasks/v1/lists/list/tasks/root")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let gc = mock_task_list_client(&server);
+    let count = TasksClient::new(&gc)
+        .complete_task_recursive("root", "list")
+        .await
+        .unwrap();
+
+    assert_eq!(count, 1);
+    child.assert_async().await;
+    root.assert_async().await;
+}
+
+#[tokio::test]
+async fn collect_up_to_limits_the_last_page_and_resumes() {
+    let mut server = mockito::Server::new_async().await;
+    let second = server
+        .mock("GET", "/tasks/v1/users/@me/lists")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("pageToken".into(), "p2".into()),
+            mockito::Matcher::UrlEncoded("maxResults".into(), "1".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "c"}], "nextPageToken": "p3"}"#)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/tasks/v1/users/@me/lists")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "maxResults".into(),
+            "2".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "a"}, {"id": "b"}], "nextPageToken": "p2"}"#)
+        .create_async()
+        .await;
+
+    let gc = mock_task_list_client(&server);
+    let task_lists = TasksClient::new(&gc)
+        .get_task_lists()
+        .max_results(2)
+        .collect_up_to(Some(3))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = task_lists
+        .items
+        .iter()
+        .map(|list| list.id.as_str())
+        .collect();
+    assert_eq!(ids, ["a", "b", "c"]);
+    assert_eq!(task_lists.next_page_token, "p3");
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn collect_up_to_zero_keeps_the_page_token() {
+    let gc = dummy_google_client_valid();
+    // Nothing is requested, the client isn't pointed at a server
+    let task_lists = TasksClient::new(&gc)
+        .get_task_lists()
+        .page_token("resume")
+        .collect_up_to(Some(0))
+        .await
+        .unwrap();
+    assert!(task_lists.items.is_empty());
+    assert_eq!(task_lists.next_page_token, "resume");
+
+    let tasks = TasksClient::new(&gc)
+        .get_tasks("list")
+        .page_token("resume")
+        .collect_up_to(Some(0))
+        .await
+        .unwrap();
+    assert_eq!(tasks.next_page_token, "resume");
+}
+
+#[tokio::test]
+async fn tasks_collect_up_to_follows_the_pages() {
+    let mut server = mockito::Server::new_async().await;
+    let second = server
+        .mock("GET", "/tasks/v1/lists/list/tasks")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "pageToken".into(),
+            "p2".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "b"}]}"#)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/tasks/v1/lists/list/tasks")
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "a"}], "nextPageToken": "p2"}"#)
+        .create_async()
+        .await;
+
+    let gc = mock_task_list_client(&server);
+    let tasks = TasksClient::new(&gc)
+        .get_tasks("list")
+        .collect_up_to(None)
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = tasks.items.iter().map(|task| task.id.as_str()).collect();
+    assert_eq!(ids, ["a", "b"]);
+    assert!(tasks.next_page_token.is_empty());
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn get_all_tasks_pages_through_every_list() {
+    let mut server = mockito::Server::new_async().await;
+    let lists_page_2 = server
+        .mock("GET", "/tasks/v1/users/@me/lists")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "pageToken".into(),
+            "l2".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "second"}]}"#)
+        .create_async()
+        .await;
+    let lists_page_1 = server
+        .mock("GET", "/tasks/v1/users/@me/lists")
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "first"}], "nextPageToken": "l2"}"#)
+        .create_async()
+        .await;
+    let first_page_2 = server
+        .mock("GET", "/tasks/v1/lists/first/tasks")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("showCompleted".into(), "false".into()),
+            mockito::Matcher::UrlEncoded("pageToken".into(), "t2".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "b"}]}"#)
+        .create_async()
+        .await;
+    let first_page_1 = server
+        .mock("GET", "/tasks/v1/lists/first/tasks")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "showCompleted".into(),
+            "false".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "a"}], "nextPageToken": "t2"}"#)
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/tasks/v1/lists/second/tasks")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "showCompleted".into(),
+            "false".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"items": [{"id": "c"}]}"#)
+        .create_async()
+        .await;
+
+    let gc = mock_task_list_client(&server);
+    let tasks = TasksClient::new(&gc)
+        .get_all_tasks()
+        .show_completed(false)
+        .request()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = tasks
+        .iter()
+        .map(|(list_id, task)| (list_id.as_str(), task.id.as_str()))
+        .collect();
+    assert_eq!(ids, [("first", "a"), ("first", "b"), ("second", "c")]);
+    for mock in [
+        lists_page_1,
+        lists_page_2,
+        first_page_1,
+        first_page_2,
+        second,
+    ] {
+        mock.assert_async().await;
+    }
+}
@@ -1,14 +1,21 @@
-use anyhow::{anyhow, Error};
+use crate::error::{Error, GoogleApiError};
 use reqwest::Method;
 use serde::de::DeserializeOwned;
 
 use crate::{
     auth::client::GoogleClient,
-    utils::request::{PaginationRequestTrait, Request},
+    utils::{
+        concurrency::{map_concurrent, DEFAULT_CONCURRENCY_LIMIT},
+        request::{
+            HeaderRequestTrait, PaginationRequestTrait, PreparedRequest, QueryParams,
+            RawParamRequestTrait, Request,
+        },
+        url::encode_segment,
+    },
 };
 
 use super::{
-    tasklist::types::TaskLists,
+    tasklist::types::{TaskList, TaskLists},
     types::{Task, TaskLink, Tasks},
 };
 
@@ -18,9 +25,15 @@ pub struct TaskInsertMode;
 pub struct TaskDeleteMode;
 pub struct TasksMode;
 pub struct TaskPatchMode;
+pub struct AllTasksMode;
 
 trait InitializedGetMode {}
 
+pub trait TaskFilterMode {}
+
+impl TaskFilterMode for TasksMode {}
+impl TaskFilterMode for AllTasksMode {}
+
 impl InitializedGetMode for TaskListMode {}
 impl InitializedGetMode for TasksMode {}
 
@@ -29,13 +42,13 @@ pub trait TaskRequestBuilderTrait {
 }
 
 pub struct TasksClient<'a, T = Uninitialized> {
-    request: Request<'a>,
+    pub(super) request: Request<'a>,
     task: Option<Task>,
     _mode: std::marker::PhantomData<T>,
 }
 
 impl<'a> TasksClient<'a, Uninitialized> {
-    pub fn new(client: &'a mut GoogleClient) -> Self {
+    pub fn new(client: &'a GoogleClient) -> Self {
         Self {
             request: Request::new(client),
             task: None,
@@ -105,12 +118,45 @@ impl<'a> TasksClient<'a, Uninitialized> {
             task: None,
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://tasks.googleapis.com/tasks/v1/lists/{task_list_id}/tasks");
+        builder.request.url = format!(
+            "https://tasks.googleapis.com/tasks/v1/lists/{}/tasks",
+            encode_segment(task_list_id)
+        );
         builder.request.method = reqwest::Method::GET;
         builder
     }
 
+    /// Get the tasks of all task lists of the user, the filters of `get_tasks` are applied to
+    /// every list.
+    ///  
+    /// # Examples
+    ///  
+    /// `Axum is used in this example, but it can be adapted to other frameworks like Actix or
+    /// Rocket.`
+    ///
+    ///``` rust
+    /// pub async fn get_tasks_due_today(State(state): State<AppState>) -> Json<Vec<Task>> {
+    ///     let google_client_guard = state.google_client.lock().await;
+    ///     let client = google_client_guard.as_ref().unwrap();
+    ///     let today = chrono::Utc::now().date_naive().and_time(chrono::NaiveTime::MIN).and_utc();
+    ///     let res = TasksClient::new(client)
+    ///         .get_all_tasks()
+    ///         .get_due_min(today)
+    ///         .get_due_max(today + chrono::Duration::days(1))
+    ///         .request()
+    ///         .await.unwrap();
+    ///
+    ///     Json(res.into_iter().map(|(_task_list_id, task)| task).collect())
+    /// }
+    ///
+    pub fn get_all_tasks(self) -> TasksClient<'a, AllTasksMode> {
+        TasksClient {
+            request: self.request,
+            task: None,
+            _mode: std::marker::PhantomData,
+        }
+    }
+
     /// Insert a task from the specified task list.
     ///  
     /// # Examples
@@ -136,8 +182,10 @@ impl<'a> TasksClient<'a, Uninitialized> {
             task: Some(Task::new()),
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://tasks.googleapis.com/tasks/v1/lists/{task_list_id}/tasks");
+        builder.request.url = format!(
+            "https://tasks.googleapis.com/tasks/v1/lists/{}/tasks",
+            encode_segment(task_list_id)
+        );
         builder.request.method = reqwest::Method::POST;
         builder
     }
@@ -169,16 +217,54 @@ impl<'a> TasksClient<'a, Uninitialized> {
             task: None,
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://tasks.googleapis.com/tasks/v1/lists/{task_list_id}/tasks/{task_id}");
+        builder.request.url = format!(
+            "https://tasks.googleapis.com/tasks/v1/lists/{}/tasks/{}",
+            encode_segment(task_list_id),
+            encode_segment(task_id)
+        );
         builder.request.method = reqwest::Method::PATCH;
         let payload = serde_json::json!({
             "status": "completed"
         });
-        builder.request.body = Some(serde_json::to_string(&payload).unwrap());
+        builder.request.body = Some(payload.to_string());
         builder
     }
 
+    /// Completes the task and all its subtasks, the subtasks first so the task is only completed
+    /// when all of them are. The tasks of the list are fetched to find the subtasks, which are
+    /// then completed with at most `DEFAULT_CONCURRENCY_LIMIT` requests at the same time.
+    ///
+    /// # Returns
+    /// * `Result<usize, Error>` - The number of completed tasks, tasks that were already
+    ///   completed aren't counted. An error when one of the tasks couldn't be completed.
+    pub async fn complete_task_recursive(
+        self,
+        task_id: &str,
+        task_list_id: &str,
+    ) -> Result<usize, Error> {
+        let client = self.request.client;
+        let mut params = QueryParams::new();
+        params.insert("showHidden".to_string(), "true".to_string());
+        let tasks = fetch_all_tasks(client, task_list_id, params).await?;
+
+        let results = map_concurrent(
+            incomplete_subtasks(&tasks, task_id),
+            DEFAULT_CONCURRENCY_LIMIT,
+            |subtask_id| complete_one(client, subtask_id, task_list_id),
+        )
+        .await;
+        let completed = results.into_iter().collect::<Result<Vec<_>, _>>()?.len();
+
+        let already_completed = tasks
+            .iter()
+            .any(|task| task.id == task_id && task.status == "completed");
+        if already_completed {
+            return Ok(completed);
+        }
+        complete_one(client, task_id, task_list_id).await?;
+        Ok(completed + 1)
+    }
+
     /// Delete a task from the specified task list.
     ///  
     /// # Examples
@@ -203,24 +289,57 @@ impl<'a> TasksClient<'a, Uninitialized> {
             task: None,
             _mode: std::marker::PhantomData,
         };
-        builder.request.url =
-            format!("https://tasks.googleapis.com/tasks/v1/lists/{task_list_id}/tasks/{task_id}");
+        builder.request.url = format!(
+            "https://tasks.googleapis.com/tasks/v1/lists/{}/tasks/{}",
+            encode_segment(task_list_id),
+            encode_segment(task_id)
+        );
         builder.request.method = reqwest::Method::DELETE;
         builder
     }
 }
 
+/// The client is left out, it holds the tokens.
+impl<T> std::fmt::Debug for TasksClient<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TasksClient")
+            .field("request", &self.request)
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
 impl<'a, T> TasksClient<'a, T> {
+    /// Renders the method, URL, query and body of the request for troubleshooting,
+    /// see `Request::debug_request`.
+    pub fn debug_request(&self) -> String {
+        self.request.debug_request(self.payload().as_deref())
+    }
+
+    /// Returns the request as it would be sent, without sending it.
+    pub fn dry_run(&self) -> Result<PreparedRequest, Error> {
+        self.request.prepare(self.body()?)
+    }
+
+    /// The JSON body the request is sent with, requests without a body ignore the task.
+    fn body(&self) -> Result<Option<String>, Error> {
+        if matches!(self.request.method, Method::GET | Method::DELETE) {
+            return Ok(None);
+        }
+        match &self.task {
+            Some(task) if self.request.method == Method::POST => {
+                Ok(Some(serde_json::to_string(task)?))
+            }
+            _ => Ok(self.request.body.clone()),
+        }
+    }
+
+    /// Same as `body` for the debug output, which can't fail.
+    fn payload(&self) -> Option<String> {
+        self.body().ok().flatten()
+    }
     pub(super) async fn make_delete_request(&mut self) -> Result<bool, Error> {
-        self.request.client.refresh_access_token_check().await?;
-        let res = self
-            .request
-            .client
-            .req_client
-            .delete(&self.request.url)
-            .query(&self.request.params)
-            .send()
-            .await?;
+        let res = self.request.send(None).await?;
 
         if res.status().is_success() {
             Ok(true)
@@ -232,65 +351,48 @@ impl<'a, T> TasksClient<'a, T> {
     where
         R: DeserializeOwned,
     {
-        self.request.client.refresh_access_token_check().await?;
-        match self.request.method {
-            Method::GET => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .get(&self.request.url)
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
-
-            Method::POST => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .post(&self.request.url)
-                    .body(serde_json::to_string(&self.task).unwrap())
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
+        if !matches!(
+            self.request.method,
+            Method::GET | Method::POST | Method::PATCH
+        ) {
+            return Err(Error::InvalidRequest("Unsupported HTTP method".to_string()));
+        }
+        // Kept on the request, so the body that was sent can be inspected afterwards
+        self.request.body = self.body()?;
+        let body = self.request.body.clone();
+        let res = self.request.send(body).await?;
 
-            Method::PATCH => {
-                let res = self
-                    .request
-                    .client
-                    .req_client
-                    .patch(&self.request.url)
-                    .body(self.request.body.clone().unwrap_or_default())
-                    .query(&self.request.params)
-                    .send()
-                    .await?;
-
-                if res.status().is_success() {
-                    Ok(Some(res.json().await?))
-                } else {
-                    Ok(None)
-                }
-            }
-            _ => Err(anyhow!("Unsupported HTTP method")),
+        if res.status().is_success() {
+            Ok(Some(self.request.read_json(res).await?))
+        } else {
+            Err(GoogleApiError::from_response(res).await.into())
         }
     }
 }
 
+impl<'a, T> RawParamRequestTrait for TasksClient<'a, T> {
+    /// Sets an arbitrary query parameter, overriding any value set by the builder methods.
+    fn raw_param(mut self, key: &str, value: &str) -> Self {
+        self.request
+            .params
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+impl<'a, T> HeaderRequestTrait for TasksClient<'a, T> {
+    /// Sets a header for this request only, overriding a client-wide value of the header.
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.request.set_header(name, value);
+        self
+    }
+
+    fn conditional(mut self) -> Self {
+        self.request.conditional = true;
+        self
+    }
+}
+
 impl<'a, T: InitializedGetMode> PaginationRequestTrait for TasksClient<'a, T> {
     /// Sets the maximum number of results to return.
     fn max_results(mut self, max: i64) -> Self {
@@ -314,6 +416,42 @@ impl<'a> TasksClient<'a, TaskListMode> {
     pub async fn request(&mut self) -> Result<Option<TaskLists>, Error> {
         self.make_request().await
     }
+
+    /// Retrieves all task lists by following the `next_page_token` of every page.
+    /// `max_results` sets the page size, a `page_token` is used as the first page.
+    ///
+    /// # Returns
+    /// * `Result<Vec<TaskList>, Error>` - The task lists of all pages, or an error when any of
+    ///   the pages couldn't be retrieved.
+    pub async fn collect_all(self) -> Result<Vec<TaskList>, Error> {
+        Ok(self.collect_up_to(None).await?.items)
+    }
+
+    /// Same as `collect_all`, but stops once `max_items` task lists are retrieved.
+    /// The page size is reduced for the last page so no task list is skipped, the returned
+    /// `next_page_token` resumes right after the last returned task list and is empty when
+    /// all task lists were retrieved.
+    pub async fn collect_up_to(mut self, max_items: Option<usize>) -> Result<TaskLists, Error> {
+        let mut task_lists = TaskLists {
+            next_page_token: self.request.page_token(),
+            ..TaskLists::default()
+        };
+        while self
+            .request
+            .limit_page_size(max_items, task_lists.items.len())
+        {
+            let Some(page) = self.request().await? else {
+                return Err(Error::Request("Failed to list the task lists".to_string()));
+            };
+            task_lists.items.extend(page.items);
+            task_lists.next_page_token = page.next_page_token;
+            if task_lists.next_page_token.is_empty() {
+                break;
+            }
+            self = self.page_token(&task_lists.next_page_token);
+        }
+        Ok(task_lists)
+    }
 }
 
 /// A client for interacting with the Google Tasks API in retrieval mode.
@@ -335,18 +473,170 @@ impl<'a> TasksClient<'a, TasksMode> {
     pub async fn request(&mut self) -> Result<Option<Tasks>, Error> {
         self.make_request().await
     }
+
+    /// Retrieves the tasks of all pages, stopping once `max_items` tasks are retrieved.
+    /// The page size is reduced for the last page so no task is skipped, the returned
+    /// `next_page_token` resumes right after the last returned task and is empty when all
+    /// tasks were retrieved.
+    pub async fn collect_up_to(mut self, max_items: Option<usize>) -> Result<Tasks, Error> {
+        let mut tasks = Tasks {
+            next_page_token: self.request.page_token(),
+            ..Tasks::default()
+        };
+        while self.request.limit_page_size(max_items, tasks.items.len()) {
+            let Some(page) = self.request().await? else {
+                return Err(Error::Request("Failed to list the tasks".to_string()));
+            };
+            tasks.items.extend(page.items);
+            tasks.next_page_token = page.next_page_token;
+            if tasks.next_page_token.is_empty() {
+                break;
+            }
+            self = self.page_token(&tasks.next_page_token);
+        }
+        Ok(tasks)
+    }
+}
+
+impl<'a> TasksClient<'a, AllTasksMode> {
+    /// Lists the task lists of the user and fetches the tasks of every list concurrently,
+    /// applying the same filters to each list. At most `DEFAULT_CONCURRENCY_LIMIT` lists are
+    /// fetched at the same time. Pages through the results of every list.
+    ///
+    /// # Returns
+    /// * `Result<Vec<(String, Task)>, Error>` - The id of the task list paired with each task,
+    ///   or the first error encountered.
+    pub async fn request(&mut self) -> Result<Vec<(String, Task)>, Error> {
+        let client = self.request.client;
+        let task_lists = TasksClient::new(client)
+            .get_task_lists()
+            .collect_all()
+            .await?;
+
+        let params = &self.request.params;
+        let results = map_concurrent(
+            task_lists,
+            DEFAULT_CONCURRENCY_LIMIT,
+            |task_list| async move {
+                let tasks = fetch_all_tasks(client, &task_list.id, params.clone()).await?;
+                Ok(tasks
+                    .into_iter()
+                    .map(|task| (task_list.id.clone(), task))
+                    .collect::<Vec<_>>())
+            },
+        )
+        .await;
+
+        let mut all_tasks = Vec::new();
+        for tasks in results {
+            all_tasks.extend(tasks?);
+        }
+        Ok(all_tasks)
+    }
+}
+
+async fn fetch_all_tasks(
+    client: &GoogleClient,
+    task_list_id: &str,
+    params: QueryParams,
+) -> Result<Vec<Task>, Error> {
+    let mut tasks = Vec::new();
+    let mut page_token = String::new();
+    loop {
+        let mut builder = TasksClient::new(client).get_tasks(task_list_id);
+        builder.request.params = params.clone();
+        builder = builder.max_results(100);
+        if !page_token.is_empty() {
+            builder = builder.page_token(&page_token);
+        }
+
+        let Some(page) = builder.request().await? else {
+            return Err(Error::Request(format!(
+                "Failed to list the tasks of task list {task_list_id}"
+            )));
+        };
+        tasks.extend(page.items);
+        if page.next_page_token.is_empty() {
+            return Ok(tasks);
+        }
+        page_token = page.next_page_token;
+    }
+}
+
+/// Ids of the subtasks of `task_id` at any depth that aren't completed yet.
+pub(super) fn incomplete_subtasks<'t>(tasks: &'t [Task], task_id: &str) -> Vec<&'t str> {
+    let mut subtasks = Vec::new();
+    let mut parents = vec![task_id];
+    while let Some(parent) = parents.pop() {
+        for task in tasks.iter().filter(|task| task.parent == parent) {
+            parents.push(&task.id);
+            if task.status != "completed" {
+                subtasks.push(task.id.as_str());
+            }
+        }
+    }
+    subtasks
+}
+
+async fn complete_one(
+    client: &GoogleClient,
+    task_id: &str,
+    task_list_id: &str,
+) -> Result<(), Error> {
+    match TasksClient::new(client)
+        .complete_task(task_id, task_list_id)
+        .request()
+        .await?
+    {
+        Some(_) => Ok(()),
+        None => Err(Error::Request(format!("Failed to complete task {task_id}"))),
+    }
+}
+
+/// Maximum length of a task title in characters.
+pub const MAX_TITLE_LENGTH: usize = 1024;
+/// Maximum length of the notes of a task in characters.
+pub const MAX_NOTES_LENGTH: usize = 8192;
+
+/// Google Tasks only keeps the UTC date of `due`, a whole date is sent as midnight UTC.
+fn whole_date_due(date: chrono::NaiveDate) -> chrono::DateTime<chrono::Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+/// Google rejects tasks exceeding the length limits with a 400 that doesn't name the field.
+fn validate_task(task: &Task) -> Result<(), Error> {
+    let title_length = task.title.chars().count();
+    if title_length > MAX_TITLE_LENGTH {
+        return Err(Error::InvalidRequest(format!(
+            "Task title is {title_length} characters, the maximum is {MAX_TITLE_LENGTH}"
+        )));
+    }
+    let notes_length = task.notes.chars().count();
+    if notes_length > MAX_NOTES_LENGTH {
+        return Err(Error::InvalidRequest(format!(
+            "Task notes are {notes_length} characters, the maximum is {MAX_NOTES_LENGTH}"
+        )));
+    }
+    Ok(())
+}
+
+/// Filters shared by the requests that list tasks.
+impl<'a, T: TaskFilterMode> TasksClient<'a, T> {
     /// Filter tasks by completion date to include only tasks completed before the specified date.
     ///
     /// # Arguments
     /// * `completed_max` - The upper bound (exclusive) for a task's completion date to filter by
     ///
+    /// Google only returns completed tasks when `showCompleted` is true, so this also sets
+    /// `show_completed(true)`.
+    ///
     /// # Returns
     /// * `Self` - Returns the client for method chaining
     pub fn get_completed_max(mut self, completed_max: chrono::DateTime<chrono::Utc>) -> Self {
         self.request
             .params
-            .insert("completedMax".to_string(), completed_max.to_string());
-        self
+            .insert("completedMax".to_string(), completed_max.to_rfc3339());
+        self.show_completed(true)
     }
 
     /// Filter tasks by completion date to include only tasks completed after the specified date.
@@ -354,13 +644,16 @@ impl<'a> TasksClient<'a, TasksMode> {
     /// # Arguments
     /// * `completed_min` - The lower bound (inclusive) for a task's completion date to filter by
     ///
+    /// Google only returns completed tasks when `showCompleted` is true, so this also sets
+    /// `show_completed(true)`.
+    ///
     /// # Returns
     /// * `Self` - Returns the client for method chaining
     pub fn get_completed_min(mut self, completed_min: chrono::DateTime<chrono::Utc>) -> Self {
         self.request
             .params
-            .insert("completedMin".to_string(), completed_min.to_string());
-        self
+            .insert("completedMin".to_string(), completed_min.to_rfc3339());
+        self.show_completed(true)
     }
 
     /// Filter tasks by due date to include only tasks due before the specified date.
@@ -373,7 +666,7 @@ impl<'a> TasksClient<'a, TasksMode> {
     pub fn get_due_max(mut self, due_max: chrono::DateTime<chrono::Utc>) -> Self {
         self.request
             .params
-            .insert("dueMax".to_string(), due_max.to_string());
+            .insert("dueMax".to_string(), due_max.to_rfc3339());
         self
     }
 
@@ -387,7 +680,7 @@ impl<'a> TasksClient<'a, TasksMode> {
     pub fn get_due_min(mut self, due_min: chrono::DateTime<chrono::Utc>) -> Self {
         self.request
             .params
-            .insert("dueMin".to_string(), due_min.to_string());
+            .insert("dueMin".to_string(), due_min.to_rfc3339());
         self
     }
 
@@ -443,7 +736,7 @@ impl<'a> TasksClient<'a, TasksMode> {
     pub fn get_updated_min(mut self, updated_min: chrono::DateTime<chrono::Utc>) -> Self {
         self.request
             .params
-            .insert("updatedMin".to_string(), updated_min.to_string());
+            .insert("updatedMin".to_string(), updated_min.to_rfc3339());
         self
     }
 
@@ -476,9 +769,13 @@ impl<'a> TasksClient<'a, TaskInsertMode> {
     /// Makes a request to create a task with the specified properties.
     ///
     /// # Returns
-    /// * `Result<Option<Tasks>, Error>` - A result containing the created task if successful,
-    ///   or an error if the request failed.
-    pub async fn request(&mut self) -> Result<Option<Tasks>, Error> {
+    /// * `Result<Option<Task>, Error>` - A result containing the created task if successful,
+    ///   or an error if the request failed. Titles longer than `MAX_TITLE_LENGTH` and notes
+    ///   longer than `MAX_NOTES_LENGTH` characters are rejected before sending the request.
+    pub async fn request(&mut self) -> Result<Option<Task>, Error> {
+        if let Some(task) = &self.task {
+            validate_task(task)?;
+        }
         self.make_request().await
     }
 
@@ -525,7 +822,7 @@ impl<'a> TasksClient<'a, TaskInsertMode> {
     /// Sets the title of the task to be created.
     ///
     /// # Arguments
-    /// * `title` - The title for the task
+    /// * `title` - The title for the task (max 1024 characters)
     ///
     /// # Returns
     /// * `Self` - Returns the client for method chaining
@@ -557,6 +854,10 @@ impl<'a> TasksClient<'a, TaskInsertMode> {
 
     /// Sets the due date of the task.
     ///
+    /// Google Tasks only keeps the date of `due`, taken in UTC, and drops the time. Local
+    /// midnight east of UTC is still the previous day in UTC, so use `set_task_due_date` or
+    /// `set_task_due_in_timezone` for dates picked by a user.
+    ///
     /// # Arguments
     /// * `due` - The due date for the task (as a DateTime)
     ///
@@ -566,6 +867,20 @@ impl<'a> TasksClient<'a, TaskInsertMode> {
         self.modify_task(|task| task.due = Some(due))
     }
 
+    /// Sets the due date of the task as the whole date, sent as midnight UTC of the date
+    /// which is how Google Tasks stores it. The task shows up on this date in every time zone.
+    pub fn set_task_due_date(self, date: chrono::NaiveDate) -> Self {
+        self.set_task_due(whole_date_due(date))
+    }
+
+    /// Sets the due date to the date `due` has in its own time zone, e.g. "today" for a user
+    /// in UTC+10 is `Utc::now().with_timezone(&their_zone)`. At 23:30 UTC it's already the next
+    /// day there, which is the date that is sent as midnight UTC. Works with any chrono
+    /// `TimeZone`, like `FixedOffset` or a `chrono_tz::Tz`. See `set_task_due_date`.
+    pub fn set_task_due_in_timezone<Tz: chrono::TimeZone>(self, due: chrono::DateTime<Tz>) -> Self {
+        self.set_task_due_date(due.date_naive())
+    }
+
     /// Sets the completion date of the task.
     ///
     /// # Arguments
@@ -611,10 +926,37 @@ impl<'a> TasksClient<'a, TaskInsertMode> {
 }
 
 impl<'a> TasksClient<'a, TaskPatchMode> {
+    /// Only patches the task when its ETag still matches, e.g. `task.etag` of the fetched task.
+    /// When the task was changed in the meantime the request fails with
+    /// `GoogleApiError::PreconditionFailed` instead of overwriting the other change.
+    pub fn set_if_match(mut self, etag: &str) -> Self {
+        self.request.set_if_match(etag);
+        self
+    }
+
+    /// Changes the due date to the date `due` has in its own time zone, see
+    /// `set_task_due_in_timezone` of the insert request.
+    pub fn set_task_due_in_timezone<Tz: chrono::TimeZone>(self, due: chrono::DateTime<Tz>) -> Self {
+        self.patch_field("due", serde_json::json!(whole_date_due(due.date_naive())))
+    }
+
+    /// Adds a field to the JSON body of the patch, keeping the fields set before.
+    fn patch_field(mut self, field: &str, value: serde_json::Value) -> Self {
+        let mut patch = self
+            .request
+            .body
+            .as_deref()
+            .and_then(|body| serde_json::from_str::<serde_json::Map<_, _>>(body).ok())
+            .unwrap_or_default();
+        patch.insert(field.to_string(), value);
+        self.request.body = Some(serde_json::Value::Object(patch).to_string());
+        self
+    }
+
     /// Makes a request to update the task with the specified properties.
     ///
     /// # Returns
-    /// * `Result<Option<Tasks>, Error>` - A result containing the updated task if successful,
+    /// * `Result<Option<Task>, Error>` - A result containing the updated task if successful,
     ///   or an error if the request failed.
     pub async fn request(&mut self) -> Result<Option<Task>, Error> {
         self.make_request().await
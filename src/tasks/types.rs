@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +38,45 @@ pub struct Tasks {
     pub items: Vec<Task>,
 }
 
+/// A task with its subtasks, see `Tasks::into_tree`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TaskNode {
+    pub task: Task,
+    pub children: Vec<TaskNode>,
+}
+
+impl Tasks {
+    /// Groups the tasks by their `parent`, the tasks on each level are sorted by `position`.
+    /// Tasks whose parent isn't in the list, e.g. because it's hidden or on another page,
+    /// are returned at the top level.
+    pub fn into_tree(self) -> Vec<TaskNode> {
+        let ids: HashSet<String> = self.items.iter().map(|task| task.id.clone()).collect();
+        let mut by_parent: HashMap<String, Vec<Task>> = HashMap::new();
+        for task in self.items {
+            let parent = if ids.contains(&task.parent) {
+                task.parent.clone()
+            } else {
+                String::new()
+            };
+            by_parent.entry(parent).or_default().push(task);
+        }
+        task_nodes(&mut by_parent, "")
+    }
+}
+
+fn task_nodes(by_parent: &mut HashMap<String, Vec<Task>>, parent: &str) -> Vec<TaskNode> {
+    let mut tasks = by_parent.remove(parent).unwrap_or_default();
+    // Positions are zero padded, so they sort as strings
+    tasks.sort_by(|a, b| a.position.cmp(&b.position));
+    tasks
+        .into_iter()
+        .map(|task| TaskNode {
+            children: task_nodes(by_parent, &task.id),
+            task,
+        })
+        .collect()
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
 pub struct Task {
     /**
@@ -184,6 +225,14 @@ pub struct Task {
      */
     #[serde(default, skip_serializing, rename = "assignmentInfo")]
     pub assignment_info: Option<AssignmentInfo>,
+
+    /**
+     * Fields returned by Google that aren't modeled by this struct.
+     * Requires the `capture-extra` feature.
+     */
+    #[cfg(feature = "capture-extra")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 impl Task {
     pub fn new() -> Self {
@@ -205,8 +254,49 @@ impl Task {
             links: Vec::new(),
             web_view_link: String::new(),
             assignment_info: None,
+            #[cfg(feature = "capture-extra")]
+            extra: std::collections::HashMap::new(),
         }
     }
+
+    /// The surface an assigned task originates from, `None` for tasks created in Tasks itself.
+    pub fn origin_surface(&self) -> Option<ContextType> {
+        self.assignment_info
+            .as_ref()
+            .map(|info| info.surface_type.clone())
+    }
+
+    /// Link to the original task in the surface it was assigned from, e.g. the comment in a Doc.
+    pub fn origin_link(&self) -> Option<&str> {
+        self.assignment_info
+            .as_ref()
+            .map(|info| info.link_to_task.as_str())
+            .filter(|link| !link.is_empty())
+    }
+
+    /// Id of the Drive file a task assigned from Docs originates from.
+    pub fn drive_file_id(&self) -> Option<&str> {
+        self.assignment_info
+            .as_ref()
+            .and_then(|info| info.drive_resource_info.as_ref())
+            .map(|drive| drive.drive_file_id.as_str())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// The Chat space a task assigned from Chat originates from, formatted as "spaces/{space}".
+    pub fn chat_space(&self) -> Option<&str> {
+        self.assignment_info
+            .as_ref()
+            .and_then(|info| info.space_info.as_ref())
+            .map(|space| space.space.as_str())
+            .filter(|space| !space.is_empty())
+    }
+
+    /// The date the task is due. Google Tasks stores due dates as midnight UTC and ignores
+    /// the time, so the UTC date is the date the user picked regardless of their time zone.
+    pub fn due_date(&self) -> Option<chrono::NaiveDate> {
+        self.due.map(|due| due.date_naive())
+    }
 }
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub struct TaskLink {
@@ -239,6 +329,48 @@ pub struct TaskLink {
     pub link: String,
 }
 
+impl TaskLink {
+    pub fn new(link_type: TaskLinkType, link: &str, description: &str) -> Self {
+        Self {
+            r#type: link_type.as_str().to_string(),
+            description: description.to_string(),
+            link: link.to_string(),
+        }
+    }
+
+    /// The type of the link, `None` for types this crate doesn't know yet.
+    pub fn link_type(&self) -> Option<TaskLinkType> {
+        match self.r#type.as_str() {
+            "email" => Some(TaskLinkType::Email),
+            "generic" => Some(TaskLinkType::Generic),
+            "chat_message" => Some(TaskLinkType::ChatMessage),
+            "keep_note" => Some(TaskLinkType::KeepNote),
+            _ => None,
+        }
+    }
+}
+
+/// The type of a `TaskLink`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum TaskLinkType {
+    Email,
+    Generic,
+    ChatMessage,
+    KeepNote,
+}
+
+impl TaskLinkType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskLinkType::Email => "email",
+            TaskLinkType::Generic => "generic",
+            TaskLinkType::ChatMessage => "chat_message",
+            TaskLinkType::KeepNote => "keep_note",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub struct AssignmentInfo {
     /**
@@ -268,7 +400,11 @@ pub struct AssignmentInfo {
     pub space_info: Option<SpaceInfo>,
 }
 
+/// The surface an assigned task originates from.
+/// Values Google adds after this crate was released deserialize as `Unknown`
+/// instead of failing the whole response.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[non_exhaustive]
 pub enum ContextType {
     #[serde(rename = "CONTEXT_TYPE_UNSPECIFIED")]
     ContextTypeUnspecified,
@@ -278,6 +414,8 @@ pub enum ContextType {
     Document,
     #[serde(rename = "SPACE")]
     Space,
+    #[serde(other)]
+    Unknown,
 }
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub struct DriveResourceInfo {
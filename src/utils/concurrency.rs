@@ -0,0 +1,46 @@
+use std::future::Future;
+
+use crate::error::Error;
+use tokio::sync::Semaphore;
+
+/// Default maximum of requests that are in flight at the same time when fanning out.
+pub const DEFAULT_CONCURRENCY_LIMIT: usize = 10;
+
+/// Runs `f` for every item with at most `limit` futures in progress at the same time,
+/// this keeps fan-out requests from opening hundreds of connections against Google's rate limits.
+/// A limit of 0 is treated as 1. The results are returned in the order of the items.
+///
+/// # Examples
+///
+/// ```rust
+/// let events = map_concurrent(calendar_ids, DEFAULT_CONCURRENCY_LIMIT, |calendar_id| {
+///     let client = &client;
+///     async move {
+///         CalendarEventsClient::new(client)
+///             .get_events(&calendar_id)
+///             .request()
+///             .await
+///     }
+/// })
+/// .await;
+/// ```
+pub async fn map_concurrent<I, F, Fut, T>(items: I, limit: usize, mut f: F) -> Vec<Result<T, Error>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let semaphore = Semaphore::new(limit.max(1));
+    let semaphore = &semaphore;
+    let futures = items.into_iter().map(|item| {
+        let fut = f(item);
+        async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .map_err(|e| Error::Request(e.to_string()))?;
+            fut.await
+        }
+    });
+    futures_util::future::join_all(futures).await
+}
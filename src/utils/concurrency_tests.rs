@@ -0,0 +1,73 @@
+#![allow(clippy::unwrap_used)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use super::concurrency::map_concurrent;
+use crate::error::Error;
+
+#[tokio::test]
+async fn concurrency_limit_is_respected() {
+    let in_flight = AtomicUsize::new(0);
+    let max_in_flight = AtomicUsize::new(0);
+
+    let results = map_concurrent(0..20, 3, |_| async {
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(results.len(), 20);
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn zero_limit_runs_one_at_a_time() {
+    let in_flight = AtomicUsize::new(0);
+    let max_in_flight = AtomicUsize::new(0);
+
+    map_concurrent(0..5, 0, |_| async {
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(())
+    })
+    .await;
+
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn results_are_in_input_order() {
+    // Later items finish first
+    let results = map_concurrent(0..10u64, 10, |item| async move {
+        tokio::time::sleep(Duration::from_millis(20 - item * 2)).await;
+        Ok(item)
+    })
+    .await;
+
+    let results: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+    assert_eq!(results, (0..10).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn errors_are_kept_per_item() {
+    let results = map_concurrent(0..4, 2, |item| async move {
+        if item % 2 == 1 {
+            Err(Error::Request(format!("item {item} failed")))
+        } else {
+            Ok(item)
+        }
+    })
+    .await;
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap(), &0);
+    assert!(matches!(&results[1], Err(Error::Request(message)) if message == "item 1 failed"));
+    assert_eq!(results[2].as_ref().unwrap(), &2);
+    assert!(matches!(&results[3], Err(Error::Request(message)) if message == "item 3 failed"));
+}
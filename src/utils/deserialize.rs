@@ -24,12 +24,28 @@ pub mod deserialize_nullable_vec {
 
 pub mod deserialize_nullable_i64 {
     use serde::{self, Deserialize, Deserializer};
+
+    /// Google encodes some int64 fields as JSON strings, both forms are accepted.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(i64),
+        String(String),
+    }
+
     pub fn deserialize<'de, D>(deserializer: D) -> Result<i64, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let value: Option<i64> = Deserialize::deserialize(deserializer)?;
-        Ok(value.unwrap_or_default())
+        let value: Option<NumberOrString> = Deserialize::deserialize(deserializer)?;
+        match value {
+            Some(NumberOrString::Number(number)) => Ok(number),
+            Some(NumberOrString::String(s)) if s.is_empty() => Ok(0),
+            Some(NumberOrString::String(s)) => s
+                .parse()
+                .map_err(|_| serde::de::Error::custom(format!("Invalid integer: {s}"))),
+            None => Ok(0),
+        }
     }
 }
 
@@ -1,6 +1,11 @@
+pub mod concurrency;
+#[cfg(test)]
+mod concurrency_tests;
 pub mod default_builder;
 pub mod deserialize;
 pub mod format;
+pub mod rate_limit;
 pub mod request;
 pub mod serialize;
+pub mod url;
 pub mod validation;
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use futures_util::future::BoxFuture;
+use tokio::sync::Mutex;
+
+/// Limits the rate of requests made by a `GoogleClient`, see `GoogleClient::set_rate_limiter`.
+/// `acquire` is awaited before every request, it resolves once the request may be sent.
+///
+/// # Examples
+///
+/// ```rust
+/// struct Unlimited;
+///
+/// impl RateLimiter for Unlimited {
+///     fn acquire(&self) -> BoxFuture<'_, ()> {
+///         Box::pin(async {})
+///     }
+/// }
+/// ```
+pub trait RateLimiter: Send + Sync {
+    fn acquire(&self) -> BoxFuture<'_, ()>;
+}
+
+/// Lower rates are raised to this, it keeps the waiting time between requests finite.
+const MIN_REQUESTS_PER_SECOND: f64 = 0.001;
+
+/// Token bucket rate limiter, allows bursts up to `capacity` requests and refills at
+/// `requests_per_second`.
+pub struct TokenBucket {
+    requests_per_second: f64,
+    capacity: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a bucket that allows a burst of one second worth of requests.
+    pub fn new(requests_per_second: f64) -> Self {
+        Self::with_capacity(requests_per_second, requests_per_second.max(1.0))
+    }
+
+    /// Creates a bucket that allows bursts of up to `capacity` requests.
+    pub fn with_capacity(requests_per_second: f64, capacity: f64) -> Self {
+        let capacity = capacity.max(1.0);
+        Self {
+            requests_per_second: requests_per_second.max(MIN_REQUESTS_PER_SECOND),
+            capacity,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+}
+
+impl RateLimiter for TokenBucket {
+    fn acquire(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            // The lock is held while waiting so waiting requests are let through in order
+            let mut state = self.state.lock().await;
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * self.requests_per_second).min(self.capacity);
+            state.refilled_at = now;
+
+            if state.tokens < 1.0 {
+                let wait = (1.0 - state.tokens) / self.requests_per_second;
+                tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+                state.tokens = 1.0;
+                state.refilled_at = Instant::now();
+            }
+            state.tokens -= 1.0;
+        })
+    }
+}
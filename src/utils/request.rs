@@ -1,27 +1,267 @@
-use std::collections::HashMap;
-
+use crate::error::Error;
 use chrono::DateTime;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::auth::client::{GoogleClient, JSON_CONTENT_TYPE};
+use crate::utils::url::encode_segment;
+
+/// Query parameters of a request. Unlike a map a key can hold multiple values,
+/// which is needed for parameters like Gmail's `labelIds` that are repeated in the query string.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct QueryParams(Vec<(String, String)>);
+
+impl QueryParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of the key, replacing all values it had before.
+    pub fn insert(&mut self, key: String, value: String) {
+        self.remove(&key);
+        self.0.push((key, value));
+    }
+
+    /// Adds a value to the key, keeping the values it already had.
+    pub fn append(&mut self, key: String, value: String) {
+        self.0.push((key, value));
+    }
 
-use crate::auth::client::GoogleClient;
+    /// Returns the first value of the key.
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Returns all values of the key in the order they were added.
+    pub fn get_all<'k>(&'k self, key: &'k str) -> impl Iterator<Item = &'k String> {
+        self.0.iter().filter(move |(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.0.retain(|(k, _)| k != key);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+}
+
+/// A request as the builders would send it, returned by their `dry_run`.
+/// The authorization header is added by the client when sending and isn't included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedRequest {
+    pub method: reqwest::Method,
+    pub url: String,
+    pub query: QueryParams,
+    pub body: Option<String>,
+    pub headers: HeaderMap,
+}
 
 pub struct Request<'a> {
-    pub client: &'a mut GoogleClient,
+    pub client: &'a GoogleClient,
     pub url: String,
     pub method: reqwest::Method,
-    pub params: HashMap<String, String>,
+    pub params: QueryParams,
     pub body: Option<String>,
+    /// Content type of the body, defaults to `JSON_CONTENT_TYPE`.
+    pub content_type: String,
+    /// Headers sent with this request only, see `set_header`.
+    pub headers: HeaderMap,
+    /// The first header that couldn't be set, reported when the request is sent.
+    invalid_header: Option<String>,
+    /// Sends the cached ETag as `If-None-Match`, see `HeaderRequestTrait::conditional`.
+    pub conditional: bool,
+}
+
+/// The client is left out, it holds the tokens.
+impl std::fmt::Debug for Request<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Request")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("params", &self.params)
+            .field("body", &self.body)
+            .field("content_type", &self.content_type)
+            .field("headers", &self.headers)
+            .field("conditional", &self.conditional)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<'a> Request<'a> {
-    pub fn new(client: &'a mut GoogleClient) -> Self {
+    pub fn new(client: &'a GoogleClient) -> Self {
         Self {
             client,
             url: "".to_string(),
             method: reqwest::Method::GET,
-            params: HashMap::new(),
+            params: QueryParams::new(),
             body: None,
+            content_type: JSON_CONTENT_TYPE.to_string(),
+            headers: HeaderMap::new(),
+            invalid_header: None,
+            conditional: false,
         }
     }
+
+    /// Sets the content type of the body for endpoints that don't take JSON,
+    /// like raw MIME uploads.
+    pub fn set_content_type(&mut self, content_type: &str) {
+        self.content_type = content_type.to_string();
+    }
+
+    /// Sends `If-Match` with the ETag of the resource, Google then rejects the request with
+    /// `GoogleApiError::PreconditionFailed` when the resource changed since it was fetched.
+    pub fn set_if_match(&mut self, etag: &str) {
+        self.set_header("If-Match", etag);
+    }
+
+    /// Sets a header for this request only, replacing a previous value of the header.
+    /// An invalid name or value fails the request with `Error::InvalidRequest` when it's sent.
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        let header = HeaderName::from_bytes(name.as_bytes())
+            .ok()
+            .zip(HeaderValue::from_str(value).ok());
+        match header {
+            Some((name, value)) => {
+                self.headers.insert(name, value);
+            }
+            None => {
+                self.invalid_header
+                    .get_or_insert_with(|| format!("Invalid header {name}: {value}"));
+            }
+        }
+    }
+
+    /// Renders the request roughly as it goes over the wire, for troubleshooting:
+    /// the method and URL with query, the headers set per request and the given body.
+    /// The authorization header isn't included.
+    pub fn debug_request(&self, body: Option<&str>) -> String {
+        let mut rendered = format!("{} {}", self.method, self.url_with_query());
+        for (name, value) in &self.headers {
+            let value = value.to_str().unwrap_or("<binary>");
+            rendered.push_str(&format!("\n{name}: {value}"));
+        }
+        if let Some(body) = body {
+            rendered.push_str(&format!("\nContent-Type: {}\n\n{body}", self.content_type));
+        }
+        rendered
+    }
+
+    /// The `pageToken` the request starts at, empty for the first page.
+    pub fn page_token(&self) -> String {
+        self.params.get("pageToken").cloned().unwrap_or_default()
+    }
+
+    /// Lowers `maxResults` to the number of items still missing for `max_items`, so the next
+    /// page ends at the last wanted item and its `nextPageToken` resumes right after it.
+    /// Returns false once `max_items` items are collected.
+    pub fn limit_page_size(&mut self, max_items: Option<usize>, collected: usize) -> bool {
+        let Some(max_items) = max_items else {
+            return true;
+        };
+        let remaining = max_items.saturating_sub(collected);
+        let page_size = self
+            .params
+            .get("maxResults")
+            .and_then(|size| size.parse::<usize>().ok());
+        if remaining > 0 && page_size.is_none_or(|size| size > remaining) {
+            self.params
+                .insert("maxResults".to_string(), remaining.to_string());
+        }
+        remaining > 0
+    }
+
+    fn url_with_query(&self) -> String {
+        let query = self
+            .params
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode_segment(key), encode_segment(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        if query.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{query}", self.url)
+        }
+    }
+
+    /// Composes the request with the given body without sending it. `send` sends exactly
+    /// this, plus the authorization header.
+    pub fn prepare(&self, body: Option<String>) -> Result<PreparedRequest, Error> {
+        let (url, headers) = self.compose(body.is_some())?;
+        Ok(PreparedRequest {
+            method: self.method.clone(),
+            url,
+            query: self.params.clone(),
+            body,
+            headers,
+        })
+    }
+
+    /// The URL and headers as they are sent, shared by `prepare` and `send`.
+    fn compose(&self, has_body: bool) -> Result<(String, HeaderMap), Error> {
+        let mut headers = self.headers()?;
+        if self.conditional && !headers.contains_key(reqwest::header::IF_NONE_MATCH) {
+            let etag = self.client.cached_etag(&self.url_with_query());
+            if let Some(etag) = etag.and_then(|etag| HeaderValue::from_str(&etag).ok()) {
+                headers.insert(reqwest::header::IF_NONE_MATCH, etag);
+            }
+        }
+        let content_type = has_body.then_some(self.content_type.as_str());
+        self.client.complete_headers(&mut headers, content_type)?;
+        Ok((self.client.resolve_url(&self.url), headers))
+    }
+
+    /// The headers set per request, the client adds its own when sending.
+    fn headers(&self) -> Result<HeaderMap, Error> {
+        match &self.invalid_header {
+            Some(message) => Err(Error::InvalidRequest(message.clone())),
+            None => Ok(self.headers.clone()),
+        }
+    }
+
+    /// Sends the request with the given body through the client, this refreshes the access token
+    /// when needed and retries once when the token is rejected.
+    pub async fn send(&mut self, body: Option<String>) -> Result<reqwest::Response, Error> {
+        self.send_body(body.map(String::into_bytes)).await
+    }
+
+    /// Same as `send`, for bodies that aren't valid UTF-8 like MIME messages with binary parts.
+    pub async fn send_bytes(&mut self, body: Vec<u8>) -> Result<reqwest::Response, Error> {
+        self.send_body(Some(body)).await
+    }
+
+    async fn send_body(&mut self, body: Option<Vec<u8>>) -> Result<reqwest::Response, Error> {
+        let (url, headers) = self.compose(body.is_some())?;
+        let res = self
+            .client
+            .send_composed(self.method.clone(), &url, &self.params, body, &headers)
+            .await?;
+        if self.conditional && res.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Err(Error::NotModified);
+        }
+        Ok(res)
+    }
+
+    /// Decodes the JSON body of a successful response. For conditional requests the ETag of
+    /// the response is cached once the body is decoded, a failed read doesn't leave an ETag
+    /// behind that would report the resource as unchanged.
+    pub async fn read_json<R>(&self, res: reqwest::Response) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+    {
+        let etag = res
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(str::to_string);
+        let decoded = res.json().await?;
+        if let Some(etag) = etag.filter(|_| self.conditional) {
+            self.client.store_etag(self.url_with_query(), etag);
+        }
+        Ok(decoded)
+    }
 }
 
 pub trait PaginationRequestTrait {
@@ -29,6 +269,31 @@ pub trait PaginationRequestTrait {
     fn page_token(self, token: &str) -> Self;
 }
 
+/// Escape hatch for query parameters the builders don't model yet.
+pub trait RawParamRequestTrait {
+    fn raw_param(self, key: &str, value: &str) -> Self;
+
+    /// Attributes the quota of this request to the given end user, e.g. a tenant id,
+    /// so one user can't exhaust the per-user quota of the others.
+    fn quota_user(self, user: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.raw_param("quotaUser", user)
+    }
+}
+
+/// Headers for a single request, e.g. `X-Goog-AuthUser`, on top of the client-wide ones.
+pub trait HeaderRequestTrait {
+    fn header(self, name: &str, value: &str) -> Self;
+
+    /// Makes the request conditional: the ETag of the response is cached by URL and query and
+    /// sent as `If-None-Match` the next time the same conditional request is made. When the
+    /// resource didn't change Google responds with 304 and the request returns
+    /// `Error::NotModified`, which saves bandwidth and quota when polling.
+    fn conditional(self) -> Self;
+}
+
 pub trait TimeRequestTrait {
     fn time_min(self, max: DateTime<chrono::Utc>) -> Self;
     fn time_max(self, token: DateTime<chrono::Utc>) -> Self;